@@ -69,6 +69,66 @@ impl GradualDifficulty {
             GameMode::Mania => Mania::gradual_difficulty(difficulty, map).map(Self::Mania),
         }
     }
+
+    /// Store a snapshot of the current state every `interval` hit objects.
+    ///
+    /// Snapshots allow [`seek`] to go backwards without processing all
+    /// previous hit objects again. Smaller intervals make seeking faster but
+    /// require more memory. An interval of `0` disables snapshots.
+    ///
+    /// [`seek`]: GradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoint_interval(self, interval: usize) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.checkpoint_interval(interval)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.checkpoint_interval(interval)),
+            Self::Catch(gradual) => Self::Catch(gradual.checkpoint_interval(interval)),
+            Self::Mania(gradual) => Self::Mania(gradual.checkpoint_interval(interval)),
+        }
+    }
+
+    /// Move to the state after processing the first `idx` hit objects and
+    /// return the resulting attributes.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Values of `idx` larger than the amount of
+    /// hit objects will process all hit objects.
+    ///
+    /// Returns `None` if `idx` is `0` or the map has no hit objects.
+    ///
+    /// [`checkpoint_interval`]: GradualDifficulty::checkpoint_interval
+    pub fn seek(&mut self, idx: usize) -> Option<DifficultyAttributes> {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual.seek(idx).map(DifficultyAttributes::Osu),
+            GradualDifficulty::Taiko(gradual) => gradual.seek(idx).map(DifficultyAttributes::Taiko),
+            GradualDifficulty::Catch(gradual) => gradual.seek(idx).map(DifficultyAttributes::Catch),
+            GradualDifficulty::Mania(gradual) => gradual.seek(idx).map(DifficultyAttributes::Mania),
+        }
+    }
+
+    /// Process all hit objects that started within the given amount of
+    /// milliseconds and return the resulting attributes.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further hit object started within that time.
+    pub fn passed_time(&mut self, passed_time: f64) -> Option<DifficultyAttributes> {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual
+                .passed_time(passed_time)
+                .map(DifficultyAttributes::Osu),
+            GradualDifficulty::Taiko(gradual) => gradual
+                .passed_time(passed_time)
+                .map(DifficultyAttributes::Taiko),
+            GradualDifficulty::Catch(gradual) => gradual
+                .passed_time(passed_time)
+                .map(DifficultyAttributes::Catch),
+            GradualDifficulty::Mania(gradual) => gradual
+                .passed_time(passed_time)
+                .map(DifficultyAttributes::Mania),
+        }
+    }
 }
 
 impl Iterator for GradualDifficulty {
@@ -9,6 +9,8 @@ pub struct InspectDifficulty {
     pub mods: GameMods,
     /// Amount of passed objects for partial plays, e.g. a fail.
     pub passed_objects: Option<u32>,
+    /// Amount of passed time in milliseconds for partial plays, e.g. a fail.
+    pub passed_time: Option<f64>,
     /// Adjust the clock rate used in the calculation.
     pub clock_rate: Option<f64>,
     /// Override a beatmap's set AR.
@@ -40,6 +42,7 @@ impl InspectDifficulty {
         let Self {
             mods,
             passed_objects,
+            passed_time,
             clock_rate,
             ar,
             cs,
@@ -55,6 +58,10 @@ impl InspectDifficulty {
             difficulty = difficulty.passed_objects(passed_objects);
         }
 
+        if let Some(passed_time) = passed_time {
+            difficulty = difficulty.passed_time(passed_time);
+        }
+
         if let Some(clock_rate) = clock_rate {
             difficulty = difficulty.clock_rate(clock_rate);
         }
@@ -0,0 +1,200 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{
+        beatmap::Beatmap,
+        mods::{GameMods, Reflection},
+    },
+    osu::{Osu, OsuObjectCache, OsuPerformance},
+    Difficulty,
+};
+
+/// Stars and maximum pp of a mod combination.
+///
+/// Created through [`Difficulty::max_pp_table`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaxPp {
+    /// The mods of this entry.
+    pub mods: GameMods,
+    /// The final star rating.
+    pub stars: f64,
+    /// The performance points of a perfect play.
+    pub pp: f64,
+}
+
+pub fn max_pp_table(difficulty: &Difficulty, map: &Beatmap, mod_sets: &[GameMods]) -> Vec<MaxPp> {
+    let mut groups: Vec<(DifficultyKey, Vec<usize>)> = Vec::new();
+
+    for (i, mods) in mod_sets.iter().enumerate() {
+        let key = DifficultyKey::new(difficulty, map.mode, mods);
+
+        match groups.iter_mut().find(|(other, _)| *other == key) {
+            Some((_, indices)) => indices.push(i),
+            None => groups.push((key, vec![i])),
+        }
+    }
+
+    let mut entries: Vec<Option<MaxPp>> = vec![None; mod_sets.len()];
+    let mut cache = OsuObjectCache::default();
+
+    for (_, indices) in groups {
+        let representative = difficulty.clone().mods(mod_sets[indices[0]].clone());
+
+        if let GameMode::Osu = map.mode {
+            let all_attrs = Osu::difficulty_for_mods(
+                &representative,
+                map,
+                indices.iter().map(|&i| &mod_sets[i]),
+                &mut cache,
+            )
+            .expect("no conversion required");
+
+            for (i, attrs) in indices.into_iter().zip(all_attrs) {
+                let mods = mod_sets[i].clone();
+                let stars = attrs.stars;
+
+                let pp = OsuPerformance::from(attrs)
+                    .difficulty(difficulty.clone().mods(mods.clone()))
+                    .calculate()
+                    .expect("no conversion required")
+                    .pp;
+
+                entries[i] = Some(MaxPp { mods, stars, pp });
+            }
+        } else {
+            let attrs = representative.calculate(map);
+            let stars = attrs.stars();
+
+            for i in indices {
+                let mods = mod_sets[i].clone();
+
+                let pp = attrs
+                    .clone()
+                    .performance()
+                    .difficulty(difficulty.clone().mods(mods.clone()))
+                    .calculate()
+                    .pp();
+
+                entries[i] = Some(MaxPp { mods, stars, pp });
+            }
+        }
+    }
+
+    entries.into_iter().flatten().collect()
+}
+
+/// Everything that affects the processing of skills.
+///
+/// Mods that are not part of the key, e.g. `NF` or `RX`, are either purely
+/// visual or only considered when evaluating the processed skills.
+#[derive(PartialEq)]
+struct DifficultyKey {
+    clock_rate: u64,
+    hr: bool,
+    ez: bool,
+    reflection: Reflection,
+    hardrock_offsets: bool,
+    custom_attrs: [Option<u64>; 4],
+    mania_keys: Option<u32>,
+    // osu!standard's flashlight skill is only evaluated with `FL`, in which
+    // case it depends on `HD`
+    flashlight: Option<bool>,
+    // osu!standard's speed skill depends on `AP`
+    ap: bool,
+}
+
+impl DifficultyKey {
+    fn new(difficulty: &Difficulty, mode: GameMode, mods: &GameMods) -> Self {
+        let difficulty = difficulty.clone().mods(mods.clone());
+        let is_osu = mode == GameMode::Osu;
+
+        Self {
+            clock_rate: difficulty.get_clock_rate().to_bits(),
+            hr: mods.hr(),
+            ez: mods.ez(),
+            reflection: mods.reflection(),
+            hardrock_offsets: difficulty.get_hardrock_offsets(),
+            custom_attrs: [mods.ar(), mods.cs(), mods.hp(), mods.od()]
+                .map(|attr| attr.map(f64::to_bits)),
+            mania_keys: mods.mania_keys().map(f32::to_bits),
+            flashlight: (is_osu && mods.fl()).then(|| mods.hd()),
+            ap: is_osu && mods.ap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_separate_calculations() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        // NM, HD, HR, DT, HDHR, HDDT, RX, HDRX, HRRX, DTRX
+        let mod_sets = [0, 8, 16, 64, 24, 72, 128, 136, 144, 192];
+
+        let table = Difficulty::new().max_pp_table(&map, mod_sets);
+
+        assert_eq!(table.len(), mod_sets.len());
+
+        assert_matches_separate(&map, &table, &mod_sets);
+    }
+
+    #[test]
+    fn hidden_flashlight_in_any_order() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        // NM, HD, FL, HDFL
+        let mut mod_sets = [0, 8, 1024, 1032];
+
+        for _ in 0..2 {
+            let table = Difficulty::new().max_pp_table(&map, mod_sets);
+            assert_matches_separate(&map, &table, &mod_sets);
+
+            mod_sets.reverse();
+        }
+    }
+
+    #[test]
+    fn shares_objects_across_clock_rates() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut cache = OsuObjectCache::default();
+
+        for mods in [0, 64, 256, 16] {
+            let mods = GameMods::from(mods);
+            let difficulty = Difficulty::new().mods(mods.clone());
+
+            let attrs = Osu::difficulty_for_mods(&difficulty, &map, [&mods], &mut cache).unwrap();
+            let expected = difficulty.calculate(&map);
+
+            assert!((attrs[0].stars - expected.stars()).abs() < 1e-10);
+            assert_eq!(attrs[0].max_combo, expected.max_combo());
+        }
+
+        // NM, DT, and HT share their objects, HR is reflected
+        assert_eq!(cache.len(), 2);
+    }
+
+    fn assert_matches_separate(map: &Beatmap, table: &[MaxPp], mod_sets: &[u32]) {
+        assert_eq!(table.len(), mod_sets.len());
+
+        for (entry, &mods) in table.iter().zip(mod_sets) {
+            let stars = Difficulty::new().mods(mods).calculate(map).stars();
+            let expected = crate::Performance::from(map).mods(mods).calculate();
+
+            assert_eq!(entry.mods, GameMods::from(mods));
+            assert!(
+                (entry.stars - stars).abs() < 1e-10,
+                "{mods}: {} != {stars}",
+                entry.stars,
+            );
+            assert!(
+                (entry.pp - expected.pp()).abs() < 1e-10,
+                "{mods}: {} != {}",
+                entry.pp,
+                expected.pp()
+            );
+        }
+    }
+}
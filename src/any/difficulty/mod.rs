@@ -1,4 +1,6 @@
 use std::{
+    borrow::Cow,
+    cmp,
     fmt::{Debug, Formatter, Result as FmtResult},
     num::NonZeroU64,
 };
@@ -6,18 +8,29 @@ use std::{
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    catch::Catch,
-    mania::Mania,
-    model::{beatmap::Beatmap, mode::ConvertError, mods::GameMods},
-    osu::Osu,
-    taiko::Taiko,
+    catch::{Catch, CatchHardestSections, CatchObjectStrain, CatchStrains},
+    mania::{Mania, ManiaHardestSections, ManiaObjectStrain, ManiaStrains},
+    model::{
+        beatmap::Beatmap,
+        limits::{CalcLimits, LimitError},
+        mode::ConvertError,
+        mods::GameMods,
+    },
+    osu::{Osu, OsuHardestSections, OsuObjectStrain, OsuStrains},
+    taiko::{Taiko, TaikoHardestSections, TaikoObjectStrain, TaikoStrains},
     GradualDifficulty, GradualPerformance,
 };
 
-use super::{attributes::DifficultyAttributes, InspectDifficulty, Strains};
+use self::max_pp::MaxPp;
+
+use super::{
+    attributes::DifficultyAttributes, HardestSections, InspectDifficulty, ObjectStrains,
+    StrainTimeline, Strains,
+};
 
 pub mod gradual;
 pub mod inspect;
+pub mod max_pp;
 pub mod object;
 pub mod skills;
 
@@ -41,6 +54,7 @@ use crate::model::mode::IGameMode;
 pub struct Difficulty {
     mods: GameMods,
     passed_objects: Option<u32>,
+    passed_time: Option<f64>,
     /// Clock rate will be clamped internally between 0.01 and 100.0.
     ///
     /// Since its minimum value is 0.01, its bits are never zero.
@@ -83,6 +97,7 @@ impl Difficulty {
         Self {
             mods: GameMods::DEFAULT,
             passed_objects: None,
+            passed_time: None,
             clock_rate: None,
             ar: None,
             cs: None,
@@ -99,6 +114,7 @@ impl Difficulty {
         let Self {
             mods,
             passed_objects,
+            passed_time,
             clock_rate,
             ar,
             cs,
@@ -111,6 +127,7 @@ impl Difficulty {
         InspectDifficulty {
             mods,
             passed_objects,
+            passed_time,
             clock_rate: clock_rate.map(non_zero_u64_to_f64),
             ar,
             cs,
@@ -145,6 +162,25 @@ impl Difficulty {
         self
     }
 
+    /// Amount of passed time in milliseconds for partial plays, e.g. a fail.
+    ///
+    /// All hit objects that started until then are considered passed,
+    /// including sliders and spinners that are only partially completed.
+    ///
+    /// The time is measured as it elapses during gameplay so the clock rate
+    /// is taken into account, e.g. with DT 1000ms of gameplay cover 1500ms of
+    /// the map.
+    ///
+    /// If [`passed_objects`] is specified as well, the lower amount of passed
+    /// objects will be used.
+    ///
+    /// [`passed_objects`]: Difficulty::passed_objects
+    pub const fn passed_time(mut self, passed_time: f64) -> Self {
+        self.passed_time = Some(passed_time);
+
+        self
+    }
+
     /// Adjust the clock rate used in the calculation.
     ///
     /// If none is specified, it will take the clock rate based on the mods
@@ -284,6 +320,18 @@ impl Difficulty {
         }
     }
 
+    /// Perform the difficulty calculation if the map stays within the given
+    /// [`CalcLimits`].
+    pub fn checked_calculate(
+        &self,
+        map: &Beatmap,
+        limits: &CalcLimits,
+    ) -> Result<DifficultyAttributes, LimitError> {
+        limits.check(self, map)?;
+
+        Ok(self.calculate(map))
+    }
+
     /// Perform the difficulty calculation for a specific [`IGameMode`].
     pub fn calculate_for_mode<M: IGameMode>(
         &self,
@@ -292,6 +340,39 @@ impl Difficulty {
         M::difficulty(self, map)
     }
 
+    /// Calculate the stars and maximum pp for each of the given mod
+    /// combinations.
+    ///
+    /// Combinations that only differ in mods that don't affect the skill
+    /// processing, like `HD` or `RX`, share a single difficulty calculation.
+    /// For osu!standard maps, combinations that only differ in their clock
+    /// rate additionally share their converted hit objects. All other
+    /// settings of this [`Difficulty`] apply to every combination.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// // NM, HD, HR, DT, and their RX variants
+    /// let table = Difficulty::new().max_pp_table(&map, [0, 8, 16, 64, 128, 136, 144, 192]);
+    ///
+    /// for entry in table {
+    ///     println!("{:?}: {:.2}* {:.2}pp", entry.mods, entry.stars, entry.pp);
+    /// }
+    /// ```
+    pub fn max_pp_table<I>(&self, map: &Beatmap, mod_sets: I) -> Vec<MaxPp>
+    where
+        I: IntoIterator,
+        I::Item: Into<GameMods>,
+    {
+        let mod_sets: Vec<GameMods> = mod_sets.into_iter().map(Into::into).collect();
+
+        max_pp::max_pp_table(self, map, &mod_sets)
+    }
+
     /// Perform the difficulty calculation but instead of evaluating the skill
     /// strains, return them as is.
     ///
@@ -312,6 +393,30 @@ impl Difficulty {
         }
     }
 
+    /// Perform the strain calculation if the map stays within the given
+    /// [`CalcLimits`].
+    pub fn checked_strains(
+        &self,
+        map: &Beatmap,
+        limits: &CalcLimits,
+    ) -> Result<Strains, LimitError> {
+        limits.check(self, map)?;
+
+        Ok(self.strains(map))
+    }
+
+    /// The timing of the strain sections of [`Difficulty::strains`].
+    #[allow(clippy::missing_panics_doc)]
+    pub fn strain_timeline(&self, map: &Beatmap) -> StrainTimeline {
+        match map.mode {
+            GameMode::Osu => OsuStrains::timeline(self, map),
+            GameMode::Taiko => TaikoStrains::timeline(self, map),
+            GameMode::Catch => CatchStrains::timeline(self, map),
+            GameMode::Mania => ManiaStrains::timeline(self, map),
+        }
+        .expect("no conversion required")
+    }
+
     /// Perform the strain calculation for a specific [`IGameMode`].
     pub fn strains_for_mode<M: IGameMode>(
         &self,
@@ -320,11 +425,122 @@ impl Difficulty {
         M::strains(self, map)
     }
 
+    /// Perform the difficulty calculation and return the strain value of
+    /// each hit object for all skills.
+    ///
+    /// Unlike [`Difficulty::strains`], the values are not grouped into
+    /// sections but tagged with the index and start time of their hit object.
+    /// The first hit object(s) of a map have no strain value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty, any::ObjectStrains};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// let ObjectStrains::Osu(strains) = Difficulty::new().object_strains(&map) else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// let hardest = strains.iter().max_by(|a, b| a.aim.total_cmp(&b.aim)).unwrap();
+    /// println!("Hardest aim at {}ms (object #{})", hardest.start_time, hardest.idx);
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn object_strains(&self, map: &Beatmap) -> ObjectStrains {
+        match map.mode {
+            GameMode::Osu => ObjectStrains::Osu(
+                OsuObjectStrain::calculate(self, map).expect("no conversion required"),
+            ),
+            GameMode::Taiko => ObjectStrains::Taiko(
+                TaikoObjectStrain::calculate(self, map).expect("no conversion required"),
+            ),
+            GameMode::Catch => ObjectStrains::Catch(
+                CatchObjectStrain::calculate(self, map).expect("no conversion required"),
+            ),
+            GameMode::Mania => ObjectStrains::Mania(
+                ManiaObjectStrain::calculate(self, map).expect("no conversion required"),
+            ),
+        }
+    }
+
+    /// Perform the per-object strain calculation if the map stays within the
+    /// given [`CalcLimits`].
+    pub fn checked_object_strains(
+        &self,
+        map: &Beatmap,
+        limits: &CalcLimits,
+    ) -> Result<ObjectStrains, LimitError> {
+        limits.check(self, map)?;
+
+        Ok(self.object_strains(map))
+    }
+
+    /// Find the `count` hardest contiguous sections of a map for each skill.
+    ///
+    /// Each section contains its time range, the range of its hit objects,
+    /// and its peak strain value. Sections are sorted by their peak in
+    /// descending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty, any::HardestSections};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// let HardestSections::Osu(sections) = Difficulty::new().hardest_sections(&map, 3) else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// for section in sections.speed {
+    ///     println!(
+    ///         "{}ms-{}ms (objects {}-{}): {:.2}",
+    ///         section.map_start,
+    ///         section.map_end,
+    ///         section.first_object,
+    ///         section.last_object,
+    ///         section.peak,
+    ///     );
+    /// }
+    /// ```
+    #[allow(clippy::missing_panics_doc)]
+    pub fn hardest_sections(&self, map: &Beatmap, count: usize) -> HardestSections {
+        const EXPECT: &str = "no conversion required";
+
+        match map.mode {
+            GameMode::Osu => {
+                HardestSections::Osu(OsuHardestSections::calculate(self, map, count).expect(EXPECT))
+            }
+            GameMode::Taiko => HardestSections::Taiko(
+                TaikoHardestSections::calculate(self, map, count).expect(EXPECT),
+            ),
+            GameMode::Catch => HardestSections::Catch(
+                CatchHardestSections::calculate(self, map, count).expect(EXPECT),
+            ),
+            GameMode::Mania => HardestSections::Mania(
+                ManiaHardestSections::calculate(self, map, count).expect(EXPECT),
+            ),
+        }
+    }
+
     /// Create a gradual difficulty calculator for a [`Beatmap`].
     pub fn gradual_difficulty(self, map: &Beatmap) -> GradualDifficulty {
         GradualDifficulty::new(self, map)
     }
 
+    /// Create a gradual difficulty calculator for a [`Beatmap`] if the map
+    /// stays within the given [`CalcLimits`].
+    pub fn checked_gradual_difficulty(
+        self,
+        map: &Beatmap,
+        limits: &CalcLimits,
+    ) -> Result<GradualDifficulty, LimitError> {
+        limits.check(&self, map)?;
+
+        Ok(self.gradual_difficulty(map))
+    }
+
     /// Create a gradual difficulty calculator for a [`Beatmap`] on a specific [`IGameMode`].
     pub fn gradual_difficulty_for_mode<M: IGameMode>(
         self,
@@ -338,6 +554,18 @@ impl Difficulty {
         GradualPerformance::new(self, map)
     }
 
+    /// Create a gradual performance calculator for a [`Beatmap`] if the map
+    /// stays within the given [`CalcLimits`].
+    pub fn checked_gradual_performance(
+        self,
+        map: &Beatmap,
+        limits: &CalcLimits,
+    ) -> Result<GradualPerformance, LimitError> {
+        limits.check(&self, map)?;
+
+        Ok(self.gradual_performance(map))
+    }
+
     /// Create a gradual performance calculator for a [`Beatmap`] on a specific [`IGameMode`].
     pub fn gradual_performance_for_mode<M: IGameMode>(
         self,
@@ -359,6 +587,48 @@ impl Difficulty {
         self.passed_objects.map_or(usize::MAX, |n| n as usize)
     }
 
+    pub(crate) const fn get_passed_time(&self) -> Option<f64> {
+        self.passed_time
+    }
+
+    /// Turn the passed time into an amount of passed objects.
+    ///
+    /// `start_times` must provide the sorted start times of all objects that
+    /// count towards the amount of passed objects. It is only called if a
+    /// passed time was specified.
+    pub(crate) fn resolve_passed_time<F, I>(&self, start_times: F) -> Cow<'_, Self>
+    where
+        F: FnOnce() -> I,
+        I: IntoIterator<Item = f64>,
+    {
+        let Some(passed_time) = self.passed_time else {
+            return Cow::Borrowed(self);
+        };
+
+        let end_time = passed_time * self.get_clock_rate();
+
+        let passed = start_times()
+            .into_iter()
+            .take_while(|&start_time| start_time <= end_time)
+            .count();
+
+        let passed_objects = cmp::min(self.get_passed_objects(), passed) as u32;
+
+        Cow::Owned(Self {
+            passed_objects: Some(passed_objects),
+            passed_time: None,
+            ..self.clone()
+        })
+    }
+
+    /// Count how many of the sorted `start_times` are passed after the given
+    /// amount of milliseconds.
+    pub(crate) fn count_passed(&self, start_times: &[f64], passed_time: f64) -> usize {
+        let end_time = passed_time * self.get_clock_rate();
+
+        start_times.partition_point(|&start_time| start_time <= end_time)
+    }
+
     pub(crate) const fn get_ar(&self) -> Option<ModsDependent> {
         self.ar
     }
@@ -394,6 +664,7 @@ impl Debug for Difficulty {
         let Self {
             mods,
             passed_objects,
+            passed_time,
             clock_rate,
             ar,
             cs,
@@ -406,6 +677,7 @@ impl Debug for Difficulty {
         f.debug_struct("Difficulty")
             .field("mods", mods)
             .field("passed_objects", passed_objects)
+            .field("passed_time", passed_time)
             .field("clock_rate", &clock_rate.map(non_zero_u64_to_f64))
             .field("ar", ar)
             .field("cs", cs)
@@ -30,6 +30,9 @@ pub struct StrainSkill {
     pub curr_section_peak: f64,
     pub curr_section_end: f64,
     pub strain_peaks: StrainsVec,
+    pub object_strains: Vec<f64>,
+    /// Whether [`StrainSkill::save_object_strain`] stores strain values.
+    pub save_object_strains: bool,
 }
 
 impl Default for StrainSkill {
@@ -39,6 +42,8 @@ impl Default for StrainSkill {
             curr_section_end: 0.0,
             // mean=386.81 | median=279
             strain_peaks: StrainsVec::with_capacity(256),
+            object_strains: Vec::new(),
+            save_object_strains: false,
         }
     }
 }
@@ -55,6 +60,24 @@ impl StrainSkill {
         self.curr_section_peak = initial_strain;
     }
 
+    /// Store the strain values of all subsequent objects.
+    pub fn enable_object_strains(&mut self) {
+        self.save_object_strains = true;
+        // mean=406.72 | median=307
+        self.object_strains.reserve(256);
+    }
+
+    /// Store the strain value of the current object if enabled.
+    pub fn save_object_strain(&mut self, strain: f64) {
+        if self.save_object_strains {
+            self.object_strains.push(strain);
+        }
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.object_strains
+    }
+
     pub fn get_curr_strain_peaks(self) -> StrainsVec {
         let mut strain_peaks = self.strain_peaks;
         strain_peaks.push(self.curr_section_peak);
@@ -95,6 +118,18 @@ impl StrainDecaySkill {
         self.inner.start_new_section_from(initial_strain);
     }
 
+    pub fn enable_object_strains(&mut self) {
+        self.inner.enable_object_strains();
+    }
+
+    pub fn save_object_strain(&mut self, strain: f64) {
+        self.inner.save_object_strain(strain);
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.into_object_strains()
+    }
+
     pub fn get_curr_strain_peaks(self) -> StrainsVec {
         self.inner.get_curr_strain_peaks()
     }
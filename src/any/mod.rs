@@ -1,7 +1,8 @@
 pub use self::{
     attributes::{DifficultyAttributes, PerformanceAttributes},
     difficulty::{
-        gradual::GradualDifficulty, inspect::InspectDifficulty, Difficulty, ModsDependent,
+        gradual::GradualDifficulty, inspect::InspectDifficulty, max_pp::MaxPp, Difficulty,
+        ModsDependent,
     },
     performance::{
         gradual::GradualPerformance,
@@ -9,11 +10,13 @@ pub use self::{
         HitResultPriority, Performance,
     },
     score_state::ScoreState,
-    strains::Strains,
+    sections::{HardSection, HardestSections},
+    strains::{ObjectStrains, StrainSection, StrainTimeline, Strains},
 };
 
 mod attributes;
 pub(crate) mod difficulty;
 mod performance;
 mod score_state;
+pub(crate) mod sections;
 mod strains;
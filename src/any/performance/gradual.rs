@@ -153,6 +153,75 @@ impl GradualPerformance {
         }
     }
 
+    /// Store a snapshot of the current state every `interval` hit objects.
+    ///
+    /// See [`GradualDifficulty::checkpoint_interval`].
+    ///
+    /// [`GradualDifficulty::checkpoint_interval`]: crate::GradualDifficulty::checkpoint_interval
+    #[must_use]
+    pub fn checkpoint_interval(self, interval: usize) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.checkpoint_interval(interval)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.checkpoint_interval(interval)),
+            Self::Catch(gradual) => Self::Catch(gradual.checkpoint_interval(interval)),
+            Self::Mania(gradual) => Self::Mania(gradual.checkpoint_interval(interval)),
+        }
+    }
+
+    /// Move to the state after processing the first `idx` hit objects and
+    /// calculate the performance attributes for the resulting score state.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Returns `None` if `idx` is `0` or the map
+    /// has no hit objects.
+    ///
+    /// [`checkpoint_interval`]: GradualPerformance::checkpoint_interval
+    pub fn seek(&mut self, state: ScoreState, idx: usize) -> Option<PerformanceAttributes> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Osu),
+            GradualPerformance::Taiko(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Taiko),
+            GradualPerformance::Catch(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Catch),
+            GradualPerformance::Mania(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Mania),
+        }
+    }
+
+    /// Process all hit objects that started within the given amount of
+    /// milliseconds and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further hit object started within that time.
+    pub fn passed_time(
+        &mut self,
+        state: ScoreState,
+        passed_time: f64,
+    ) -> Option<PerformanceAttributes> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual
+                .passed_time(state.into(), passed_time)
+                .map(PerformanceAttributes::Osu),
+            GradualPerformance::Taiko(gradual) => gradual
+                .passed_time(state.into(), passed_time)
+                .map(PerformanceAttributes::Taiko),
+            GradualPerformance::Catch(gradual) => gradual
+                .passed_time(state.into(), passed_time)
+                .map(PerformanceAttributes::Catch),
+            GradualPerformance::Mania(gradual) => gradual
+                .passed_time(state.into(), passed_time)
+                .map(PerformanceAttributes::Mania),
+        }
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
@@ -1,7 +1,11 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    catch::CatchPerformance, mania::ManiaPerformance, osu::OsuPerformance, taiko::TaikoPerformance,
+    catch::CatchPerformance,
+    mania::ManiaPerformance,
+    model::limits::{CalcLimits, LimitError},
+    osu::OsuPerformance,
+    taiko::TaikoPerformance,
     Difficulty, GameMods,
 };
 
@@ -67,6 +71,24 @@ impl<'map> Performance<'map> {
         }
     }
 
+    /// Consume the performance calculator and calculate performance
+    /// attributes if the map stays within the given [`CalcLimits`].
+    ///
+    /// Calculators that were created through attributes always pass.
+    pub fn checked_calculate(
+        self,
+        limits: &CalcLimits,
+    ) -> Result<PerformanceAttributes, LimitError> {
+        match self {
+            Self::Osu(ref o) => o.check_limits(limits)?,
+            Self::Taiko(ref t) => t.check_limits(limits)?,
+            Self::Catch(ref f) => f.check_limits(limits)?,
+            Self::Mania(ref m) => m.check_limits(limits)?,
+        }
+
+        Ok(self.calculate())
+    }
+
     /// Attempt to convert the map to the specified mode.
     ///
     /// Returns `Err(self)` if the conversion is incompatible or no beatmap is
@@ -151,6 +173,23 @@ impl<'map> Performance<'map> {
         }
     }
 
+    /// Amount of passed time in milliseconds for partial plays, e.g. a fail.
+    ///
+    /// Only takes effect if the difficulty attributes still have to be
+    /// calculated, i.e. if a beatmap was provided instead of attributes.
+    ///
+    /// See [`Difficulty::passed_time`] for more information.
+    ///
+    /// [`Difficulty::passed_time`]: crate::Difficulty::passed_time
+    pub fn passed_time(self, passed_time: f64) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.passed_time(passed_time)),
+            Self::Taiko(t) => Self::Taiko(t.passed_time(passed_time)),
+            Self::Catch(f) => Self::Catch(f.passed_time(passed_time)),
+            Self::Mania(m) => Self::Mania(m.passed_time(passed_time)),
+        }
+    }
+
     /// Adjust the clock rate used in the calculation.
     ///
     /// If none is specified, it will take the clock rate based on the mods
@@ -0,0 +1,203 @@
+use crate::{
+    catch::CatchHardestSections, mania::ManiaHardestSections, osu::OsuHardestSections,
+    taiko::TaikoHardestSections,
+};
+
+use super::StrainTimeline;
+
+/// A contiguous part of a map that is particularly hard for a skill.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HardSection {
+    /// Start of the section in map time in ms.
+    pub map_start: f64,
+    /// End of the section in map time in ms.
+    pub map_end: f64,
+    /// Start of the section in real time in ms.
+    pub real_start: f64,
+    /// End of the section in real time in ms.
+    pub real_end: f64,
+    /// Index of the first hit object within the section.
+    pub first_object: usize,
+    /// Index of the last hit object within the section.
+    pub last_object: usize,
+    /// The highest strain value of a hit object within the section.
+    pub peak: f64,
+}
+
+/// The hardest sections of a map for each skill.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HardestSections {
+    Osu(OsuHardestSections),
+    Taiko(TaikoHardestSections),
+    Catch(CatchHardestSections),
+    Mania(ManiaHardestSections),
+}
+
+macro_rules! from_mode_sections {
+    ( $mode:ident: $sections:ident ) => {
+        impl From<$sections> for HardestSections {
+            fn from(sections: $sections) -> Self {
+                Self::$mode(sections)
+            }
+        }
+    };
+}
+
+from_mode_sections!(Osu: OsuHardestSections);
+from_mode_sections!(Taiko: TaikoHardestSections);
+from_mode_sections!(Catch: CatchHardestSections);
+from_mode_sections!(Mania: ManiaHardestSections);
+
+/// Neighbouring strain sections are merged into a hard section as long as
+/// their peak is at least this fraction of the hard section's peak.
+const MERGE_THRESHOLD: f64 = 0.75;
+
+#[derive(Copy, Clone)]
+struct SectionObjects {
+    first: usize,
+    last: usize,
+    peak: f64,
+    taken: bool,
+}
+
+/// Find the `count` hardest sections based on the strain values of objects.
+///
+/// Objects are given as `(index, map start time, strain)` and are expected
+/// to be sorted by start time. Objects are grouped into the strain sections
+/// of the timeline; starting at the section with the highest strain,
+/// neighbouring sections are merged until their peak drops too far.
+pub(crate) fn hardest_sections<I>(
+    timeline: &StrainTimeline,
+    objects: I,
+    count: usize,
+) -> Vec<HardSection>
+where
+    I: IntoIterator<Item = (usize, f64, f64)>,
+{
+    let mut sections: Vec<Option<SectionObjects>> = Vec::new();
+
+    for (idx, start_time, strain) in objects {
+        let Some(section_idx) = timeline.section_at(start_time) else {
+            continue;
+        };
+
+        if sections.len() <= section_idx {
+            sections.resize(section_idx + 1, None);
+        }
+
+        match sections[section_idx] {
+            Some(ref mut section) => {
+                section.last = idx;
+                section.peak = section.peak.max(strain);
+            }
+            None => {
+                sections[section_idx] = Some(SectionObjects {
+                    first: idx,
+                    last: idx,
+                    peak: strain,
+                    taken: false,
+                });
+            }
+        }
+    }
+
+    let mut order: Vec<_> = sections
+        .iter()
+        .enumerate()
+        .filter_map(|(i, section)| section.filter(|s| s.peak > 0.0).map(|s| (i, s.peak)))
+        .collect();
+
+    order.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let mut hardest = Vec::with_capacity(count.min(order.len()));
+
+    for (i, peak) in order {
+        if hardest.len() == count {
+            break;
+        }
+
+        if sections[i].is_some_and(|s| s.taken) {
+            continue;
+        }
+
+        let threshold = peak * MERGE_THRESHOLD;
+
+        let mergeable = |section: &Option<SectionObjects>| {
+            section.is_some_and(|s| !s.taken && s.peak >= threshold)
+        };
+
+        let mut start = i;
+
+        while start > 0 && mergeable(&sections[start - 1]) {
+            start -= 1;
+        }
+
+        let mut end = i;
+
+        while end + 1 < sections.len() && mergeable(&sections[end + 1]) {
+            end += 1;
+        }
+
+        let merged = &mut sections[start..=end];
+
+        let mut first_object = usize::MAX;
+        let mut last_object = 0;
+
+        for section in merged.iter_mut().flatten() {
+            section.taken = true;
+            first_object = first_object.min(section.first);
+            last_object = last_object.max(section.last);
+        }
+
+        let first = timeline.section(start);
+        let last = timeline.section(end);
+
+        hardest.push(HardSection {
+            map_start: first.map_start,
+            map_end: last.map_end,
+            real_start: first.real_start,
+            real_end: last.real_end,
+            first_object,
+            last_object,
+            peak,
+        });
+    }
+
+    hardest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_neighbouring_sections() {
+        let timeline = StrainTimeline::new(Some(100.0), 400.0, 1.0);
+
+        let objects = [
+            (1, 100.0, 1.0),
+            (2, 500.0, 4.0),
+            (3, 900.0, 10.0),
+            (4, 1300.0, 8.0),
+            (5, 1700.0, 2.0),
+            (6, 2100.0, 6.0),
+        ];
+
+        let sections = hardest_sections(&timeline, objects, 3);
+
+        assert_eq!(sections.len(), 3);
+
+        assert_eq!(sections[0].first_object, 3);
+        assert_eq!(sections[0].last_object, 4);
+        assert!((sections[0].peak - 10.0).abs() < f64::EPSILON);
+        assert!((sections[0].map_start - 800.0).abs() < f64::EPSILON);
+        assert!((sections[0].map_end - 1600.0).abs() < f64::EPSILON);
+
+        assert_eq!(sections[1].first_object, 6);
+        assert_eq!(sections[1].last_object, 6);
+
+        // Sections are not merged across an already taken section
+        assert_eq!(sections[2].first_object, 2);
+        assert_eq!(sections[2].last_object, 2);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{catch::CatchStrains, mania::ManiaStrains, osu::OsuStrains, taiko::TaikoStrains};
+use crate::{
+    catch::{CatchObjectStrain, CatchStrains},
+    mania::{ManiaObjectStrain, ManiaStrains},
+    osu::{OsuObjectStrain, OsuStrains},
+    taiko::{TaikoObjectStrain, TaikoStrains},
+};
 
 /// The result of calculating the strains on a map.
 ///
@@ -23,6 +28,126 @@ impl Strains {
     }
 }
 
+/// Start and end of a strain section.
+///
+/// Map time refers to timestamps in the map itself while real time is
+/// adjusted by the clock rate, e.g. halved for DT.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StrainSection {
+    /// Start of the section in map time in ms.
+    pub map_start: f64,
+    /// End of the section in map time in ms.
+    pub map_end: f64,
+    /// Start of the section in real time in ms.
+    pub real_start: f64,
+    /// End of the section in real time in ms.
+    pub real_end: f64,
+}
+
+/// The timing of strain sections.
+///
+/// Strain sections have a constant length in real time. The first section
+/// ends at the first multiple of the section length that is at or after the
+/// first difficulty object.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StrainTimeline {
+    /// Start of the first section in real time in ms.
+    pub first_section_start: f64,
+    /// Length of a section in real time in ms.
+    pub section_len: f64,
+    /// The clock rate that was used to calculate the strains.
+    pub clock_rate: f64,
+}
+
+impl StrainTimeline {
+    /// Create a new timeline based on the map time of the first hit object
+    /// that has a difficulty object.
+    pub(crate) fn new(first_start_time: Option<f64>, section_len: f64, clock_rate: f64) -> Self {
+        let first_section_end = first_start_time.map_or(0.0, |start_time| {
+            (start_time / clock_rate / section_len).ceil() * section_len
+        });
+
+        Self {
+            first_section_start: first_section_end - section_len,
+            section_len,
+            clock_rate,
+        }
+    }
+
+    /// The start and end of the section at index `idx`.
+    pub fn section(&self, idx: usize) -> StrainSection {
+        let real_start = self.first_section_start + idx as f64 * self.section_len;
+        let real_end = real_start + self.section_len;
+
+        StrainSection {
+            map_start: real_start * self.clock_rate,
+            map_end: real_end * self.clock_rate,
+            real_start,
+            real_end,
+        }
+    }
+
+    /// The start and end of the first `count` sections.
+    pub fn sections(&self, count: usize) -> impl ExactSizeIterator<Item = StrainSection> + '_ {
+        (0..count).map(|idx| self.section(idx))
+    }
+
+    /// Pair up each strain value with the section it belongs to.
+    pub fn zip<'a>(
+        &'a self,
+        strains: &'a [f64],
+    ) -> impl ExactSizeIterator<Item = (StrainSection, f64)> + 'a {
+        self.sections(strains.len()).zip(strains.iter().copied())
+    }
+
+    /// Index of the section that contains the given map time.
+    ///
+    /// Just like during the difficulty calculation, a section contains its
+    /// end but not its start. Returns `None` if the time lies at or before the
+    /// start of the first section. The index may exceed the amount of strain
+    /// values if the time lies after the last section.
+    pub fn section_at(&self, map_time: f64) -> Option<usize> {
+        let offset = (map_time / self.clock_rate - self.first_section_start) / self.section_len;
+
+        (offset > 0.0).then(|| offset.ceil() as usize - 1)
+    }
+
+    /// Resample strain values into buckets of `interval` ms in map time,
+    /// starting at map time `0`.
+    ///
+    /// Each bucket holds the highest strain value of all sections overlapping
+    /// with it, or `0.0` if there are none. Since buckets are aligned in map
+    /// time, resampled strains of the same map with different clock rates
+    /// line up index by index.
+    ///
+    /// Returns an empty list if `interval` is not positive.
+    pub fn resample(&self, strains: &[f64], interval: f64) -> Vec<f64> {
+        if strains.is_empty() || interval <= 0.0 || interval.is_nan() {
+            return Vec::new();
+        }
+
+        let map_end = self.section(strains.len() - 1).map_end;
+        let bucket_count = (map_end / interval).ceil().max(0.0) as usize;
+
+        (0..bucket_count)
+            .map(|i| {
+                let bucket_start = i as f64 * interval;
+                let bucket_end = bucket_start + interval;
+
+                let real_start = bucket_start / self.clock_rate - self.first_section_start;
+                let real_end = bucket_end / self.clock_rate - self.first_section_start;
+
+                let first = (real_start / self.section_len).floor().max(0.0) as usize;
+                let last = ((real_end / self.section_len).ceil() as usize).min(strains.len());
+
+                strains
+                    .get(first..last)
+                    .map_or(0.0, |strains| strains.iter().copied().fold(0.0, f64::max))
+            })
+            .collect()
+    }
+}
+
 macro_rules! from_mode_strains {
     ( $mode:ident: $strains:ident ) => {
         impl From<$strains> for Strains {
@@ -37,3 +162,44 @@ from_mode_strains!(Osu: OsuStrains);
 from_mode_strains!(Taiko: TaikoStrains);
 from_mode_strains!(Catch: CatchStrains);
 from_mode_strains!(Mania: ManiaStrains);
+
+/// The strain values of each hit object of a map.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObjectStrains {
+    Osu(Vec<OsuObjectStrain>),
+    Taiko(Vec<TaikoObjectStrain>),
+    Catch(Vec<CatchObjectStrain>),
+    Mania(Vec<ManiaObjectStrain>),
+}
+
+impl ObjectStrains {
+    /// The amount of hit objects with strain values.
+    pub const fn len(&self) -> usize {
+        match self {
+            ObjectStrains::Osu(strains) => strains.len(),
+            ObjectStrains::Taiko(strains) => strains.len(),
+            ObjectStrains::Catch(strains) => strains.len(),
+            ObjectStrains::Mania(strains) => strains.len(),
+        }
+    }
+
+    /// Whether there are no hit objects with strain values.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+macro_rules! from_mode_object_strains {
+    ( $mode:ident: $strain:ident ) => {
+        impl From<Vec<$strain>> for ObjectStrains {
+            fn from(strains: Vec<$strain>) -> Self {
+                Self::$mode(strains)
+            }
+        }
+    };
+}
+
+from_mode_object_strains!(Osu: OsuObjectStrain);
+from_mode_object_strains!(Taiko: TaikoObjectStrain);
+from_mode_object_strains!(Catch: CatchObjectStrain);
+from_mode_object_strains!(Mania: ManiaObjectStrain);
@@ -15,6 +15,12 @@ pub struct CatchDifficultyAttributes {
     pub n_droplets: u32,
     /// The amount of tiny droplets.
     pub n_tiny_droplets: u32,
+    /// The amount of fruits and droplets that require a hyperdash to be
+    /// reached.
+    pub n_hyper_dashes: u32,
+    /// The amount of fruits and droplets that are barely reached without
+    /// hyperdash, i.e. caught with the edge of the catcher.
+    pub n_edge_dashes: u32,
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -54,6 +60,14 @@ impl CatchDifficultyAttributes {
 
         self.n_tiny_droplets += count.tiny_droplets;
     }
+
+    pub(crate) const fn add_dash(&mut self, hyper_dash: bool, edge_dash: bool) {
+        if hyper_dash {
+            self.n_hyper_dashes += 1;
+        } else if edge_dash {
+            self.n_edge_dashes += 1;
+        }
+    }
 }
 
 /// The result of a performance calculation on an osu!catch map.
@@ -113,6 +127,18 @@ pub struct GradualObjectCount {
     tiny_droplets: u32,
 }
 
+impl GradualObjectCount {
+    /// Whether the palpable object is a fruit or a droplet.
+    pub const fn is_fruit(self) -> bool {
+        self.fruit
+    }
+
+    /// The amount of tiny droplets preceding the palpable object.
+    pub const fn tiny_droplets(self) -> u32 {
+        self.tiny_droplets
+    }
+}
+
 pub enum ObjectCountBuilder {
     Regular {
         count: ObjectCount,
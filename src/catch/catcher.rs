@@ -4,12 +4,19 @@ const AREA_CATCHER_SIZE: f32 = 106.75;
 
 impl Catcher {
     pub const BASE_SPEED: f64 = 1.0;
+    pub const BASE_WALK_SPEED: f64 = 0.5;
     pub const ALLOWED_CATCH_RANGE: f32 = 0.8;
 
     pub fn calculate_catch_width(cs: f32) -> f32 {
         Self::calculate_catch_width_by_scale(Self::calculate_scale(cs))
     }
 
+    /// Objects whose distance to a hyperdash is at most this value are
+    /// caught with the outer quarter of the catcher.
+    pub fn edge_dash_max_dist(cs: f32) -> f32 {
+        Self::calculate_catch_width(cs) * 0.25
+    }
+
     fn calculate_catch_width_by_scale(scale: f32) -> f32 {
         AREA_CATCHER_SIZE * scale.abs() * Self::ALLOWED_CATCH_RANGE
     }
@@ -2,7 +2,7 @@ use rosu_map::section::{general::GameMode, hit_objects::CurveBuffers};
 
 use crate::{
     model::{
-        beatmap::Beatmap,
+        beatmap::{Beatmap, BeatmapStatistics},
         hit_object::{HitObject, HitObjectKind, HoldNote, Spinner},
         mods::Reflection,
     },
@@ -10,7 +10,7 @@ use crate::{
 };
 
 use super::{
-    attributes::ObjectCountBuilder,
+    attributes::{CatchDifficultyAttributes, ObjectCountBuilder},
     catcher::Catcher,
     object::{
         banana_shower::BananaShower,
@@ -77,6 +77,49 @@ pub fn convert_objects(
     palpable_objects
 }
 
+pub fn statistics(map: &Beatmap, stats: &mut BeatmapStatistics) {
+    let mut count = ObjectCountBuilder::new_regular(usize::MAX);
+
+    let mut bufs = JuiceStreamBufs {
+        curve: CurveBuffers::default(),
+        nested_objects: Vec::with_capacity(16),
+        ticks: Vec::new(),
+    };
+
+    for h in map.hit_objects.iter() {
+        let end_time = match h.kind {
+            HitObjectKind::Circle => {
+                stats.n_circles += 1;
+
+                h.start_time
+            }
+            HitObjectKind::Slider(_) => {
+                stats.n_sliders += 1;
+
+                h.start_time
+            }
+            HitObjectKind::Spinner(Spinner { duration })
+            | HitObjectKind::Hold(HoldNote { duration }) => {
+                stats.n_spinners += 1;
+
+                h.start_time + duration
+            }
+        };
+
+        let end_time = convert_object(h, map, &mut count, &mut bufs)
+            .map(|nested| nested.start_time)
+            .fold(end_time, f64::max);
+
+        stats.last_object_time = stats.last_object_time.max(end_time);
+    }
+
+    let mut attrs = CatchDifficultyAttributes::default();
+    attrs.set_object_count(&count.into_regular());
+
+    stats.max_combo = attrs.max_combo();
+    stats.n_slider_ticks = attrs.n_droplets;
+}
+
 fn convert_object<'a>(
     h: &'a HitObject,
     map: &Beatmap,
@@ -123,11 +166,15 @@ impl Iterator for ObjectIter<'_> {
         match self.state {
             ObjectIterState::Fruit(ref mut fruit) => fruit
                 .take()
-                .map(|fruit| PalpableObject::new(self.x, fruit.x_offset, self.start_time)),
+                .map(|fruit| PalpableObject::new(self.x, fruit.x_offset, self.start_time, true)),
             ObjectIterState::JuiceStream(ref mut stream) => stream
                 .nested_objects
                 .find(|nested| !matches!(nested.kind, NestedJuiceStreamObjectKind::TinyDroplet))
-                .map(|nested| PalpableObject::new(nested.pos, 0.0, nested.start_time)),
+                .map(|nested| {
+                    let fruit = matches!(nested.kind, NestedJuiceStreamObjectKind::Fruit);
+
+                    PalpableObject::new(nested.pos, 0.0, nested.start_time, fruit)
+                }),
             ObjectIterState::BananaShower(_) => None,
         }
     }
@@ -10,6 +10,7 @@ use crate::{
         CatchDifficultyAttributes,
     },
     model::mode::ConvertError,
+    util::checkpoints::Checkpoints,
     Beatmap, Difficulty,
 };
 
@@ -60,12 +61,22 @@ pub struct CatchGradualDifficulty {
     attrs: CatchDifficultyAttributes,
     /// The delta of object counts after each palpable object
     count: Vec<GradualObjectCount>,
+    start_times: Box<[f64]>,
     diff_objects: Box<[CatchDifficultyObject]>,
     movement: Movement,
+    checkpoints: Checkpoints<Snapshot>,
+}
+
+struct Snapshot {
+    attrs: CatchDifficultyAttributes,
+    movement: Movement,
 }
 
 impl CatchGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!catch maps.
+    ///
+    /// Objects past [`Difficulty::passed_objects`] or [`Difficulty::passed_time`]
+    /// are not processed.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
         let map = map.convert_ref(GameMode::Catch, difficulty.get_mods())?;
 
@@ -86,24 +97,152 @@ impl CatchGradualDifficulty {
             map_attrs.cs as f32,
         );
 
+        let mut start_times: Vec<f64> = palpable_objects.iter().map(|h| h.start_time).collect();
+
+        let take = difficulty
+            .resolve_passed_time(|| start_times.iter().copied())
+            .get_passed_objects();
+
+        start_times.truncate(take);
+
         let diff_objects = DifficultyValues::create_difficulty_objects(
             &map_attrs,
             clock_rate,
-            palpable_objects.iter(),
+            palpable_objects.iter().take(take),
         );
-
         let count = count.into_gradual();
         let movement = Movement::new(clock_rate);
 
+        let checkpoints = Checkpoints::new(Snapshot {
+            attrs: attrs.clone(),
+            movement: movement.clone(),
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
             attrs,
             count,
+            start_times: start_times.into_boxed_slice(),
             diff_objects,
             movement,
+            checkpoints,
         })
     }
+
+    /// Store a snapshot of the current state every `interval` fruits and droplets.
+    ///
+    /// Snapshots allow [`seek`] to go backwards without processing all
+    /// previous fruits and droplets again. Smaller intervals make seeking faster but
+    /// require more memory. An interval of `0` disables snapshots.
+    ///
+    /// [`seek`]: CatchGradualDifficulty::seek
+    #[must_use]
+    pub const fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the state after processing the first `idx` fruits and droplets and
+    /// return the resulting attributes.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Values of `idx` larger than the amount of
+    /// fruits and droplets will process all fruits and droplets.
+    ///
+    /// Returns `None` if `idx` is `0` or the map has no fruits and droplets.
+    ///
+    /// [`checkpoint_interval`]: CatchGradualDifficulty::checkpoint_interval
+    pub fn seek(&mut self, idx: usize) -> Option<CatchDifficultyAttributes> {
+        let Some(n) = idx.checked_sub(1) else {
+            self.restore(0);
+
+            return None;
+        };
+
+        if n < self.idx {
+            self.restore(n);
+        }
+
+        self.nth(n - self.idx)
+    }
+
+    /// Process all fruits and droplets that started within the given amount
+    /// of milliseconds and return the resulting attributes.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further fruit or droplet started within that time.
+    pub fn passed_time(&mut self, passed_time: f64) -> Option<CatchDifficultyAttributes> {
+        self.remaining_until(passed_time)
+            .checked_sub(1)
+            .and_then(|n| self.nth(n))
+    }
+
+    /// Amount of fruits and droplets that remain to be processed until the
+    /// given amount of milliseconds passed.
+    pub(crate) fn remaining_until(&self, passed_time: f64) -> usize {
+        self.difficulty
+            .count_passed(&self.start_times, passed_time)
+            .saturating_sub(self.idx)
+    }
+
+    /// The object count of the next palpable object.
+    pub(crate) fn next_object_count(&self) -> Option<GradualObjectCount> {
+        if self.idx < self.start_times.len() {
+            self.count.get(self.idx).copied()
+        } else {
+            None
+        }
+    }
+
+    fn restore(&mut self, idx: usize) {
+        let (idx, snapshot) = self.checkpoints.latest_until(idx);
+
+        self.idx = idx;
+        self.attrs.clone_from(&snapshot.attrs);
+        self.movement = snapshot.movement.clone();
+    }
+
+    fn record_checkpoint(&mut self) {
+        let Self {
+            idx,
+            attrs,
+            movement,
+            checkpoints,
+            ..
+        } = self;
+
+        checkpoints.record(*idx, || Snapshot {
+            attrs: attrs.clone(),
+            movement: movement.clone(),
+        });
+    }
+
+    /// Process the next `take` palpable objects without evaluating them.
+    fn advance(&mut self, mut take: usize) {
+        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
+
+        // The first palpable object has no difficulty object
+        if self.idx == 0 && take > 0 {
+            take -= 1;
+            self.attrs.add_object_count(self.count[self.idx]);
+            self.idx += 1;
+        }
+
+        let mut movement = Skill::new(&mut self.movement, &self.diff_objects);
+
+        for curr in skip_iter.take(take) {
+            movement.process(curr);
+            self.attrs
+                .add_dash(curr.last_object.hyper_dash, curr.last_object.edge_dash);
+
+            self.attrs.add_object_count(self.count[self.idx]);
+            self.idx += 1;
+        }
+    }
 }
 
 impl Iterator for CatchGradualDifficulty {
@@ -117,12 +256,15 @@ impl Iterator for CatchGradualDifficulty {
         if self.idx > 0 {
             let curr = self.diff_objects.get(self.idx - 1)?;
             Skill::new(&mut self.movement, &self.diff_objects).process(curr);
-        } else if self.count.is_empty() {
+            self.attrs
+                .add_dash(curr.last_object.hyper_dash, curr.last_object.edge_dash);
+        } else if self.start_times.is_empty() {
             return None;
         }
 
         self.attrs.add_object_count(self.count[self.idx]);
         self.idx += 1;
+        self.record_checkpoint();
 
         let mut attrs = self.attrs.clone();
 
@@ -139,24 +281,13 @@ impl Iterator for CatchGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
-
         let mut take = cmp::min(n, self.len().saturating_sub(1));
 
-        // The first palpable object has no difficulty object
-        if self.idx == 0 && take > 0 {
-            take -= 1;
-            self.attrs.add_object_count(self.count[self.idx]);
-            self.idx += 1;
-        }
-
-        let mut movement = Skill::new(&mut self.movement, &self.diff_objects);
-
-        for curr in skip_iter.take(take) {
-            movement.process(curr);
-
-            self.attrs.add_object_count(self.count[self.idx]);
-            self.idx += 1;
+        while take > 0 {
+            let step = cmp::min(take, self.checkpoints.steps_until_due(self.idx));
+            self.advance(step);
+            self.record_checkpoint();
+            take -= step;
         }
 
         self.next()
@@ -165,7 +296,8 @@ impl Iterator for CatchGradualDifficulty {
 
 impl ExactSizeIterator for CatchGradualDifficulty {
     fn len(&self) -> usize {
-        self.diff_objects.len() + 1 - self.idx
+        // Only contains the start times of objects that will be processed
+        self.start_times.len() - self.idx
     }
 }
 
@@ -219,4 +351,75 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn passed_time() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = CatchGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        for passed_time in [5000.0, 12345.0, 30000.0] {
+            let expected = difficulty
+                .clone()
+                .passed_time(passed_time)
+                .calculate_for_mode::<Catch>(&map)
+                .unwrap();
+
+            assert_eq!(gradual.passed_time(passed_time), Some(expected));
+        }
+
+        assert!(gradual.passed_time(30000.0).is_none());
+    }
+
+    #[test]
+    fn stops_at_difficulty_passed_time() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64).passed_time(12345.0);
+        let gradual = CatchGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        let expected = difficulty.calculate_for_mode::<Catch>(&map).unwrap();
+
+        assert_eq!(gradual.last(), Some(expected));
+    }
+
+    #[test]
+    fn passed_time_caps_remaining_objects() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().passed_time(12345.0);
+        let gradual = CatchGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.remaining_until(f64::MAX), gradual.len());
+
+        // Nothing passed before the first object
+        let difficulty = Difficulty::new().passed_time(-1.0);
+        let mut gradual = CatchGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.next(), None);
+    }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new();
+        let mut gradual = CatchGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .checkpoint_interval(50);
+
+        let len = gradual.len();
+
+        for idx in [len / 2, 10, len, 1, 2, 137, 0, 99, 100] {
+            let expected = idx.checked_sub(1).and_then(|n| {
+                CatchGradualDifficulty::new(difficulty.clone(), &map)
+                    .unwrap()
+                    .nth(n)
+            });
+
+            assert_eq!(gradual.seek(idx), expected, "idx={idx}");
+        }
+    }
 }
@@ -18,6 +18,7 @@ use super::{
 
 pub mod gradual;
 mod object;
+pub mod palpable;
 mod skills;
 
 const DIFFICULTY_MULTIPLIER: f64 = 4.59;
@@ -57,6 +58,23 @@ impl CatchDifficultySetup {
     }
 }
 
+/// Start times of all palpable objects, i.e. fruits and droplets.
+pub(crate) fn start_times(difficulty: &Difficulty, map: &Beatmap) -> Vec<f64> {
+    let map_attrs = map.attributes().difficulty(difficulty).build();
+    let mut count = ObjectCountBuilder::new_gradual();
+
+    convert_objects(
+        map,
+        &mut count,
+        difficulty.get_mods().reflection(),
+        difficulty.get_hardrock_offsets(),
+        map_attrs.cs as f32,
+    )
+    .iter()
+    .map(|h| h.start_time)
+    .collect()
+}
+
 pub struct DifficultyValues {
     pub movement: Movement,
     pub attrs: CatchDifficultyAttributes,
@@ -64,9 +82,22 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Self {
-        let take = difficulty.get_passed_objects();
-        let clock_rate = difficulty.get_clock_rate();
+        Self::calculate_with(difficulty, map, false).0
+    }
 
+    /// Same as [`DifficultyValues::calculate`] but the movement skill also
+    /// stores the strain value of each object.
+    ///
+    /// Also returns the start times of all palpable objects.
+    pub fn calculate_object_strains(difficulty: &Difficulty, map: &Beatmap) -> (Self, Vec<f64>) {
+        Self::calculate_with(difficulty, map, true)
+    }
+
+    fn calculate_with(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        object_strains: bool,
+    ) -> (Self, Vec<f64>) {
         let CatchDifficultySetup {
             map_attrs,
             mut attrs,
@@ -74,11 +105,37 @@ impl DifficultyValues {
 
         let hr_offsets = difficulty.get_hardrock_offsets();
         let reflection = difficulty.get_mods().reflection();
-        let mut count = ObjectCountBuilder::new_regular(take);
+
+        // Per-object counts are only required to resolve the passed time
+        let mut count = if difficulty.get_passed_time().is_some() {
+            ObjectCountBuilder::new_gradual()
+        } else {
+            ObjectCountBuilder::new_regular(difficulty.get_passed_objects())
+        };
 
         let palpable_objects =
             convert_objects(map, &mut count, reflection, hr_offsets, map_attrs.cs as f32);
 
+        let difficulty =
+            &*difficulty.resolve_passed_time(|| palpable_objects.iter().map(|h| h.start_time));
+        let take = difficulty.get_passed_objects();
+        let clock_rate = difficulty.get_clock_rate();
+
+        match count {
+            ObjectCountBuilder::Regular { .. } => attrs.set_object_count(&count.into_regular()),
+            ObjectCountBuilder::Gradual { .. } => {
+                for count in count.into_gradual().into_iter().take(take) {
+                    attrs.add_object_count(count);
+                }
+            }
+        }
+
+        let start_times = if object_strains {
+            palpable_objects.iter().map(|h| h.start_time).collect()
+        } else {
+            Vec::new()
+        };
+
         let diff_objects = Self::create_difficulty_objects(
             &map_attrs,
             clock_rate,
@@ -87,17 +144,20 @@ impl DifficultyValues {
 
         let mut movement = Movement::new(clock_rate);
 
+        if object_strains {
+            movement.enable_object_strains();
+        }
+
         {
             let mut movement = Skill::new(&mut movement, &diff_objects);
 
             for curr in diff_objects.iter() {
                 movement.process(curr);
+                attrs.add_dash(curr.last_object.hyper_dash, curr.last_object.edge_dash);
             }
         }
 
-        attrs.set_object_count(&count.into_regular());
-
-        Self { movement, attrs }
+        (Self { movement, attrs }, start_times)
     }
 
     pub fn eval(attrs: &mut CatchDifficultyAttributes, movement_difficulty_value: f64) {
@@ -117,6 +177,7 @@ impl DifficultyValues {
         half_catcher_width *= 1.0 - ((map_attrs.cs as f32 - 5.5).max(0.0) * 0.0625);
         let scaling_factor =
             CatchDifficultyObject::NORMALIZED_HITOBJECT_RADIUS / half_catcher_width;
+        let edge_dash_max_dist = Catcher::edge_dash_max_dist(map_attrs.cs as f32);
 
         palpable_objects
             .enumerate()
@@ -126,6 +187,7 @@ impl DifficultyValues {
                     last_object,
                     clock_rate,
                     scaling_factor,
+                    edge_dash_max_dist,
                     i,
                 );
                 last_object = hit_object;
@@ -18,6 +18,7 @@ impl CatchDifficultyObject {
         last_object: &PalpableObject,
         clock_rate: f64,
         scaling_factor: f32,
+        edge_dash_max_dist: f32,
         idx: usize,
     ) -> Self {
         let normalized_pos = hit_object.effective_x() * scaling_factor;
@@ -30,6 +31,7 @@ impl CatchDifficultyObject {
         let last_object = LastObject {
             hyper_dash: last_object.hyper_dash,
             dist_to_hyper_dash: last_object.dist_to_hyper_dash,
+            edge_dash: last_object.edge_dash(edge_dash_max_dist),
         };
 
         Self {
@@ -44,9 +46,11 @@ impl CatchDifficultyObject {
     }
 }
 
+#[allow(clippy::struct_field_names)]
 pub struct LastObject {
     pub hyper_dash: bool,
     pub dist_to_hyper_dash: f32,
+    pub edge_dash: bool,
 }
 
 impl IDifficultyObject for CatchDifficultyObject {
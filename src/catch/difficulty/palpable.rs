@@ -0,0 +1,158 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    catch::{attributes::ObjectCountBuilder, catcher::Catcher, convert::convert_objects},
+    model::mode::ConvertError,
+    Beatmap, Difficulty,
+};
+
+use super::CatchDifficultySetup;
+
+/// The kind of an osu!catch palpable object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CatchPalpableKind {
+    Fruit,
+    Droplet,
+}
+
+/// A fruit or droplet of an osu!catch map and how the catcher reaches the
+/// next one.
+///
+/// Positions include mod adjustments such as HR's offsets and reflection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatchPalpableObject {
+    /// Whether the object is a fruit or a droplet.
+    pub kind: CatchPalpableKind,
+    /// Start time of the object in map time in ms.
+    pub start_time: f64,
+    /// The x position of the object.
+    pub x: f32,
+    /// Whether the catcher needs to hyperdash to reach the next object.
+    pub hyper_dash: bool,
+    /// The remaining distance the catcher could still dash before reaching
+    /// the next object would require a hyperdash.
+    ///
+    /// The catcher's width is taken into account. The value is `0.0` for
+    /// hyperdashes and for the last object.
+    pub dist_to_hyper_dash: f32,
+    /// Whether the next object is barely reached without hyperdash, i.e.
+    /// caught with the outer quarter of the catcher whose width depends on
+    /// the circle size.
+    pub edge_dash: bool,
+}
+
+impl CatchPalpableObject {
+    /// Convert the map's hit objects into fruits and droplets, sorted by
+    /// their start time.
+    ///
+    /// Tiny droplets and bananas are not included.
+    /// [`Difficulty::passed_objects`] and [`Difficulty::passed_time`] are
+    /// respected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::catch::CatchPalpableObject;
+    ///
+    /// let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+    /// let objects = CatchPalpableObject::calculate(&Difficulty::new().mods(16), &map).unwrap();
+    ///
+    /// for h in objects.iter().filter(|h| h.hyper_dash) {
+    ///     println!("Hyperdash at {}ms from x={}", h.start_time, h.x);
+    /// }
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Vec<Self>, ConvertError> {
+        let map = map.convert_ref(GameMode::Catch, difficulty.get_mods())?;
+
+        let CatchDifficultySetup { map_attrs, .. } = CatchDifficultySetup::new(difficulty, &map);
+
+        let palpable_objects = convert_objects(
+            &map,
+            &mut ObjectCountBuilder::new_regular(0),
+            difficulty.get_mods().reflection(),
+            difficulty.get_hardrock_offsets(),
+            map_attrs.cs as f32,
+        );
+
+        let take = difficulty
+            .resolve_passed_time(|| palpable_objects.iter().map(|h| h.start_time))
+            .get_passed_objects();
+
+        let last_idx = palpable_objects.len().saturating_sub(1);
+        let edge_dash_max_dist = Catcher::edge_dash_max_dist(map_attrs.cs as f32);
+
+        let objects = palpable_objects
+            .iter()
+            .enumerate()
+            .take(take)
+            .map(|(i, h)| Self {
+                kind: if h.fruit {
+                    CatchPalpableKind::Fruit
+                } else {
+                    CatchPalpableKind::Droplet
+                },
+                start_time: h.start_time,
+                x: h.effective_x(),
+                hyper_dash: h.hyper_dash,
+                dist_to_hyper_dash: h.dist_to_hyper_dash,
+                edge_dash: i < last_idx && h.edge_dash(edge_dash_max_dist),
+            })
+            .collect();
+
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catch::Catch;
+
+    use super::*;
+
+    #[test]
+    fn dashes_match_attributes() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        for difficulty in [
+            Difficulty::new(),
+            Difficulty::new().mods(16),
+            Difficulty::new().passed_objects(300),
+        ] {
+            let objects = CatchPalpableObject::calculate(&difficulty, &map).unwrap();
+            let attrs = difficulty.calculate_for_mode::<Catch>(&map).unwrap();
+
+            let n_fruits = objects
+                .iter()
+                .filter(|h| h.kind == CatchPalpableKind::Fruit)
+                .count();
+
+            assert_eq!(n_fruits, attrs.n_fruits as usize);
+            assert_eq!(objects.len() - n_fruits, attrs.n_droplets as usize);
+
+            // Dashes towards objects that were not passed are not counted
+            let dashes = &objects[..objects.len() - 1];
+            let n_hyper_dashes = dashes.iter().filter(|h| h.hyper_dash).count();
+            let n_edge_dashes = dashes.iter().filter(|h| h.edge_dash).count();
+
+            assert_eq!(n_hyper_dashes, attrs.n_hyper_dashes as usize);
+            assert_eq!(n_edge_dashes, attrs.n_edge_dashes as usize);
+        }
+    }
+
+    #[test]
+    fn edge_dashes_depend_on_catcher_width() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        for cs in [2.0, 5.0, 8.0] {
+            let difficulty = Difficulty::new().cs(cs, false);
+            let objects = CatchPalpableObject::calculate(&difficulty, &map).unwrap();
+            let max_dist = Catcher::calculate_catch_width(cs) * 0.25;
+
+            for h in &objects[..objects.len() - 1] {
+                let expected = !h.hyper_dash && h.dist_to_hyper_dash <= max_dist;
+                assert_eq!(h.edge_dash, expected, "CS{cs} at {}ms", h.start_time);
+            }
+        }
+    }
+}
@@ -18,6 +18,7 @@ const DECAY_WEIGHT: f64 = 0.94;
 
 const SECTION_LEN: f64 = 750.0;
 
+#[derive(Clone)]
 pub struct Movement {
     inner: StrainDecaySkill,
     last_player_pos: Option<f32>,
@@ -112,6 +113,14 @@ impl Movement {
         self.inner.get_curr_strain_peaks()
     }
 
+    pub fn enable_object_strains(&mut self) {
+        self.inner.enable_object_strains();
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.into_object_strains()
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
@@ -169,6 +178,8 @@ impl<'a> Skill<'a, Movement> {
         }
 
         let strain_value_at = self.inner.strain_value_at(curr);
+
+        self.inner.inner.save_object_strain(strain_value_at);
         *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
     }
 }
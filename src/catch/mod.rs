@@ -10,12 +10,22 @@ use crate::{
 
 pub use self::{
     attributes::{CatchDifficultyAttributes, CatchPerformanceAttributes},
-    difficulty::gradual::CatchGradualDifficulty,
-    performance::{gradual::CatchGradualPerformance, CatchPerformance},
+    difficulty::{
+        gradual::CatchGradualDifficulty,
+        palpable::{CatchPalpableKind, CatchPalpableObject},
+    },
+    performance::{
+        gradual::CatchGradualPerformance,
+        judgement::{CatchJudgement, CatchJudgementError, CatchJudgementPerformance},
+        CatchPerformance,
+    },
     score_state::CatchScoreState,
-    strains::CatchStrains,
+    simulation::{CatcherMovement, CatcherSimulation, CatcherStep},
+    strains::{CatchHardestSections, CatchObjectStrain, CatchStrains},
 };
 
+pub(crate) use self::convert::statistics;
+
 mod attributes;
 mod catcher;
 mod convert;
@@ -23,6 +33,7 @@ mod difficulty;
 mod object;
 mod performance;
 mod score_state;
+mod simulation;
 mod strains;
 
 const PLAYFIELD_WIDTH: f32 = 512.0;
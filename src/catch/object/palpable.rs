@@ -6,20 +6,30 @@ pub struct PalpableObject {
     pub start_time: f64,
     pub dist_to_hyper_dash: f32,
     pub hyper_dash: bool,
+    pub fruit: bool,
 }
 
 impl PalpableObject {
-    pub const fn new(x: f32, x_offset: f32, start_time: f64) -> Self {
+    pub const fn new(x: f32, x_offset: f32, start_time: f64, fruit: bool) -> Self {
         Self {
             x,
             x_offset,
             start_time,
             dist_to_hyper_dash: 0.0,
             hyper_dash: false,
+            fruit,
         }
     }
 
     pub fn effective_x(&self) -> f32 {
         (self.x + self.x_offset).clamp(0.0, PLAYFIELD_WIDTH)
     }
+
+    /// Whether the catcher barely reaches the next object without hyperdash.
+    ///
+    /// Only meaningful if there is a next object. `max_dist` is given by
+    /// `Catcher::edge_dash_max_dist`.
+    pub fn edge_dash(&self, max_dist: f32) -> bool {
+        !self.hyper_dash && self.dist_to_hyper_dash <= max_dist
+    }
 }
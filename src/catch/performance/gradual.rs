@@ -1,5 +1,8 @@
 use crate::{
-    catch::{CatchGradualDifficulty, CatchPerformanceAttributes, CatchScoreState},
+    catch::{
+        attributes::GradualObjectCount, CatchGradualDifficulty, CatchPerformanceAttributes,
+        CatchScoreState,
+    },
     model::mode::ConvertError,
     Beatmap, Difficulty,
 };
@@ -130,11 +133,76 @@ impl CatchGradualPerformance {
         Some(performance)
     }
 
+    /// Store a snapshot of the current state every `interval` hit objects.
+    ///
+    /// See [`CatchGradualDifficulty::checkpoint_interval`].
+    ///
+    /// [`CatchGradualDifficulty::checkpoint_interval`]: crate::catch::CatchGradualDifficulty::checkpoint_interval
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoint_interval(interval);
+
+        self
+    }
+
+    /// Move to the state after processing the first `idx` hit objects and
+    /// calculate the performance attributes for the resulting score state.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Returns `None` if `idx` is `0` or the map
+    /// has no hit objects.
+    ///
+    /// [`checkpoint_interval`]: CatchGradualPerformance::checkpoint_interval
+    #[allow(clippy::missing_panics_doc)]
+    pub fn seek(
+        &mut self,
+        state: CatchScoreState,
+        idx: usize,
+    ) -> Option<CatchPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .seek(idx)?
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate()
+            .expect("no conversion required");
+
+        Some(performance)
+    }
+
+    /// Process all hit objects that started within the given amount of
+    /// milliseconds and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further hit object started within that time.
+    pub fn passed_time(
+        &mut self,
+        state: CatchScoreState,
+        passed_time: f64,
+    ) -> Option<CatchPerformanceAttributes> {
+        let n = self
+            .difficulty
+            .remaining_until(passed_time)
+            .checked_sub(1)?;
+
+        self.nth(state, n)
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.difficulty.len()
     }
+
+    /// The object count of the next palpable object.
+    pub(crate) fn next_object_count(&self) -> Option<GradualObjectCount> {
+        self.difficulty.next_object_count()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,266 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::{gradual::CatchGradualPerformance, CatchPerformanceAttributes, CatchScoreState};
+
+/// A single judgement of a fruit, droplet, or tiny droplet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CatchJudgement {
+    /// A fruit was caught.
+    Fruit,
+    /// A droplet was caught.
+    Droplet,
+    /// A tiny droplet was caught.
+    TinyDroplet,
+    /// A tiny droplet was missed.
+    TinyDropletMiss,
+    /// A fruit or droplet was missed.
+    Miss,
+}
+
+/// Error type when a [`CatchJudgement`] does not fit the current object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CatchJudgementError {
+    /// All fruits and droplets have already been judged
+    NoRemainingObjects,
+    /// The judgement requires a fruit but the current object is a droplet
+    NotAFruit,
+    /// The judgement requires a droplet but the current object is a fruit
+    NotADroplet,
+    /// All tiny droplets preceding the current object have already been
+    /// judged
+    TooManyTinyDroplets { max: u32 },
+}
+
+impl Error for CatchJudgementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for CatchJudgementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            CatchJudgementError::NoRemainingObjects => {
+                f.write_str("All fruits and droplets have already been judged")
+            }
+            CatchJudgementError::NotAFruit => f.write_str("The current object is not a fruit"),
+            CatchJudgementError::NotADroplet => f.write_str("The current object is not a droplet"),
+            CatchJudgementError::TooManyTinyDroplets { max } => {
+                write!(
+                    f,
+                    "The current object is only preceded by {max} tiny droplets"
+                )
+            }
+        }
+    }
+}
+
+/// Gradually calculate the performance attributes of an osu!catch map
+/// based on individual judgements.
+///
+/// Unlike [`CatchGradualPerformance`], this type keeps track of the score
+/// state itself. Judgements are passed in the order they occur in-game, i.e.
+/// the tiny droplets leading up to a fruit or droplet are judged through
+/// [`CatchJudgement::TinyDroplet`] and [`CatchJudgement::TinyDropletMiss`]
+/// before the fruit or droplet itself.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::catch::{CatchJudgement, CatchJudgementPerformance};
+///
+/// let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+///
+/// let difficulty = Difficulty::new().mods(64); // DT
+/// let mut gradual = CatchJudgementPerformance::new(difficulty, &map).unwrap();
+///
+/// // The first fruit or droplet is missed
+/// let attrs = gradual.judge(CatchJudgement::Miss).unwrap().unwrap();
+/// println!("PP: {}", attrs.pp);
+///
+/// assert_eq!(gradual.state().misses, 1);
+/// assert_eq!(gradual.state().max_combo, 0);
+/// ```
+pub struct CatchJudgementPerformance {
+    gradual: CatchGradualPerformance,
+    state: CatchScoreState,
+    curr_combo: u32,
+    tiny_droplets: u32,
+}
+
+impl CatchJudgementPerformance {
+    /// Create a new judgement-based performance calculator for osu!catch
+    /// maps.
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let gradual = CatchGradualPerformance::new(difficulty, map)?;
+
+        Ok(Self {
+            gradual,
+            state: CatchScoreState::new(),
+            curr_combo: 0,
+            tiny_droplets: 0,
+        })
+    }
+
+    /// Process a judgement of the current object.
+    ///
+    /// Returns the performance attributes once a fruit or droplet is
+    /// completed, i.e. for [`CatchJudgement::Fruit`],
+    /// [`CatchJudgement::Droplet`], and [`CatchJudgement::Miss`].
+    ///
+    /// Judgements of tiny droplets only update the state and return
+    /// `Ok(None)`.
+    pub fn judge(
+        &mut self,
+        judgement: CatchJudgement,
+    ) -> Result<Option<CatchPerformanceAttributes>, CatchJudgementError> {
+        let count = self
+            .gradual
+            .next_object_count()
+            .ok_or(CatchJudgementError::NoRemainingObjects)?;
+
+        match judgement {
+            CatchJudgement::TinyDroplet | CatchJudgement::TinyDropletMiss => {
+                let max = count.tiny_droplets();
+
+                if self.tiny_droplets == max {
+                    return Err(CatchJudgementError::TooManyTinyDroplets { max });
+                }
+
+                self.tiny_droplets += 1;
+
+                if judgement == CatchJudgement::TinyDroplet {
+                    self.state.tiny_droplets += 1;
+                } else {
+                    self.state.tiny_droplet_misses += 1;
+                }
+
+                return Ok(None);
+            }
+            CatchJudgement::Fruit if !count.is_fruit() => {
+                return Err(CatchJudgementError::NotAFruit)
+            }
+            CatchJudgement::Droplet if count.is_fruit() => {
+                return Err(CatchJudgementError::NotADroplet)
+            }
+            CatchJudgement::Fruit => self.state.fruits += 1,
+            CatchJudgement::Droplet => self.state.droplets += 1,
+            CatchJudgement::Miss => self.state.misses += 1,
+        }
+
+        if judgement == CatchJudgement::Miss {
+            self.curr_combo = 0;
+        } else {
+            self.curr_combo += 1;
+            self.state.max_combo = self.state.max_combo.max(self.curr_combo);
+        }
+
+        // Tiny droplets that were not judged count as missed
+        self.state.tiny_droplet_misses += count.tiny_droplets() - self.tiny_droplets;
+        self.tiny_droplets = 0;
+
+        Ok(self.gradual.next(self.state.clone()))
+    }
+
+    /// The current score state, including judgements of tiny droplets
+    /// preceding the current object.
+    pub const fn state(&self) -> &CatchScoreState {
+        &self.state
+    }
+
+    /// Returns the amount of remaining fruits and droplets.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.gradual.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catch::CatchPerformance;
+
+    use super::*;
+
+    #[test]
+    fn full_combo_matches_ss() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let mut gradual = CatchJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        let mut last = None;
+
+        while let Some(count) = gradual.gradual.next_object_count() {
+            for _ in 0..count.tiny_droplets() {
+                let attrs = gradual.judge(CatchJudgement::TinyDroplet).unwrap();
+                assert!(attrs.is_none());
+            }
+
+            let judgement = if count.is_fruit() {
+                CatchJudgement::Fruit
+            } else {
+                CatchJudgement::Droplet
+            };
+
+            last = gradual.judge(judgement).unwrap();
+            assert!(last.is_some());
+        }
+
+        let expected = CatchPerformance::from(&map).calculate().unwrap();
+
+        assert_eq!(last.unwrap(), expected);
+        assert_eq!(
+            gradual.judge(CatchJudgement::Fruit),
+            Err(CatchJudgementError::NoRemainingObjects)
+        );
+    }
+
+    #[test]
+    fn validates_judgements() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let mut gradual = CatchJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        // Skip to the first droplet with preceding tiny droplets
+        loop {
+            let count = gradual.gradual.next_object_count().unwrap();
+
+            if !count.is_fruit() && count.tiny_droplets() > 0 {
+                break;
+            }
+
+            let judgement = if count.is_fruit() {
+                CatchJudgement::Fruit
+            } else {
+                CatchJudgement::Droplet
+            };
+
+            gradual.judge(judgement).unwrap();
+        }
+
+        let combo = gradual.curr_combo;
+        let tiny_droplets = gradual.state().tiny_droplets;
+
+        assert_eq!(
+            gradual.judge(CatchJudgement::Fruit),
+            Err(CatchJudgementError::NotAFruit)
+        );
+
+        assert!(gradual
+            .judge(CatchJudgement::TinyDropletMiss)
+            .unwrap()
+            .is_none());
+        assert_eq!(gradual.curr_combo, combo);
+
+        // The remaining tiny droplets count as missed
+        assert!(gradual.judge(CatchJudgement::Miss).unwrap().is_some());
+        assert_eq!(gradual.curr_combo, 0);
+        assert_eq!(gradual.state().tiny_droplets, tiny_droplets);
+        assert_eq!(gradual.state().misses, 1);
+    }
+}
@@ -0,0 +1,283 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::{catcher::Catcher, CatchPalpableObject, PLAYFIELD_WIDTH};
+
+/// How the catcher reaches a fruit or droplet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CatcherMovement {
+    /// The object can be caught by walking or without moving at all.
+    Walk,
+    /// The object can only be caught by dashing.
+    Dash,
+    /// The object can only be caught through the hyperdash of the previous
+    /// object.
+    HyperDash,
+    /// The object can not be caught even when dashing and the previous
+    /// object does not provide a hyperdash.
+    Impossible,
+}
+
+/// The catcher's movement towards a single fruit or droplet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatcherStep {
+    /// Start time of the object in map time in ms.
+    pub start_time: f64,
+    /// The x position of the object.
+    pub x: f32,
+    /// The minimum distance the catcher has to move since the previous
+    /// object to catch this one.
+    pub required_dist: f64,
+    /// Time in ms since the previous object, adjusted by the clock rate.
+    ///
+    /// For the first object, this is the time since the start of the map.
+    pub available_time: f64,
+    /// How the catcher reaches the object.
+    pub movement: CatcherMovement,
+}
+
+impl CatcherStep {
+    /// The speed in distance per ms, adjusted by the clock rate, that the
+    /// catcher requires to reach the object.
+    pub fn required_speed(&self) -> f64 {
+        if self.required_dist <= 0.0 {
+            0.0
+        } else if self.available_time <= 0.0 {
+            f64::INFINITY
+        } else {
+            self.required_dist / self.available_time
+        }
+    }
+
+    /// Whether the object can be caught without dashing.
+    pub fn walkable(&self) -> bool {
+        self.movement == CatcherMovement::Walk
+    }
+}
+
+/// Simulation of the catcher moving across the fruits and droplets of an
+/// osu!catch map.
+///
+/// Instead of following a single path, the simulation keeps track of all
+/// catcher positions from which every previous object could have been
+/// caught. Hence, an object is only considered to require a dash or
+/// hyperdash if no possible path allows catching it otherwise.
+///
+/// The catcher's speed scales with the clock rate so whether an object can
+/// be reached does not depend on it; only the durations and speeds do.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatcherSimulation {
+    /// The catcher's movement towards each fruit and droplet, sorted by time.
+    pub steps: Vec<CatcherStep>,
+    /// The width in which the catcher catches objects.
+    pub catch_width: f32,
+    /// The clock rate that was used.
+    pub clock_rate: f64,
+}
+
+impl CatcherSimulation {
+    /// Simulate the catcher on a map.
+    ///
+    /// [`Difficulty::passed_objects`] and [`Difficulty::passed_time`] are
+    /// respected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::catch::CatcherSimulation;
+    ///
+    /// let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+    /// let simulation = CatcherSimulation::calculate(&Difficulty::new().mods(16), &map).unwrap();
+    ///
+    /// let walkable = simulation.steps.iter().filter(|step| step.walkable()).count();
+    /// println!("{walkable}/{} objects can be walked", simulation.steps.len());
+    ///
+    /// for idx in simulation.impossible() {
+    ///     println!("Object at {}ms can not be caught", simulation.steps[idx].start_time);
+    /// }
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = map.convert_ref(GameMode::Catch, difficulty.get_mods())?;
+        let objects = CatchPalpableObject::calculate(difficulty, &map)?;
+
+        let cs = map.attributes().difficulty(difficulty).build().cs;
+        let catch_width = Catcher::calculate_catch_width(cs as f32);
+
+        Ok(Self::new(
+            &objects,
+            catch_width,
+            difficulty.get_clock_rate(),
+        ))
+    }
+
+    fn new(objects: &[CatchPalpableObject], catch_width: f32, clock_rate: f64) -> Self {
+        let half_catch_width = f64::from(catch_width) * 0.5;
+        let playfield_width = f64::from(PLAYFIELD_WIDTH);
+
+        // Range of positions that the catcher may be at
+        let center = playfield_width * 0.5;
+        let mut catcher = (center, center);
+
+        let mut prev: Option<&CatchPalpableObject> = None;
+        let mut steps = Vec::with_capacity(objects.len());
+
+        for h in objects {
+            let x = f64::from(h.x);
+            let catchable = (x - half_catch_width, x + half_catch_width);
+
+            let time = h.start_time - prev.map_or(0.0, |prev| prev.start_time);
+
+            let required_dist = (catchable.0 - catcher.1)
+                .max(catcher.0 - catchable.1)
+                .max(0.0);
+
+            let movement = if required_dist <= time * Catcher::BASE_WALK_SPEED {
+                CatcherMovement::Walk
+            } else if required_dist <= time * Catcher::BASE_SPEED {
+                CatcherMovement::Dash
+            } else if prev.is_some_and(|prev| prev.hyper_dash) {
+                CatcherMovement::HyperDash
+            } else {
+                CatcherMovement::Impossible
+            };
+
+            catcher = match movement {
+                CatcherMovement::Walk | CatcherMovement::Dash => {
+                    let reach = time * Catcher::BASE_SPEED;
+
+                    (
+                        (catcher.0 - reach).max(catchable.0).max(0.0),
+                        (catcher.1 + reach).min(catchable.1).min(playfield_width),
+                    )
+                }
+                // Hyperdashes move the catcher right onto the object
+                CatcherMovement::HyperDash => (x, x),
+                // Assume the object was caught anyway to keep going
+                CatcherMovement::Impossible => {
+                    (catchable.0.max(0.0), catchable.1.min(playfield_width))
+                }
+            };
+
+            steps.push(CatcherStep {
+                start_time: h.start_time,
+                x: h.x,
+                required_dist,
+                available_time: time / clock_rate,
+                movement,
+            });
+
+            prev = Some(h);
+        }
+
+        Self {
+            steps,
+            catch_width,
+            clock_rate,
+        }
+    }
+
+    /// The catcher's walking speed in distance per ms, adjusted by the clock
+    /// rate.
+    pub fn walk_speed(&self) -> f64 {
+        Catcher::BASE_WALK_SPEED * self.clock_rate
+    }
+
+    /// The catcher's dashing speed in distance per ms, adjusted by the clock
+    /// rate.
+    pub fn dash_speed(&self) -> f64 {
+        Catcher::BASE_SPEED * self.clock_rate
+    }
+
+    /// Indices of all objects that can not be reached without hyperdash,
+    /// regardless of whether the map provides one.
+    pub fn requires_hyper_dash(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter_map(|(i, step)| (step.movement >= CatcherMovement::HyperDash).then_some(i))
+    }
+
+    /// Indices of all objects that can not be caught.
+    pub fn impossible(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter_map(|(i, step)| (step.movement == CatcherMovement::Impossible).then_some(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catch::CatchPalpableKind;
+
+    use super::*;
+
+    #[test]
+    fn hyper_dashes_are_required() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let difficulty = Difficulty::new().mods(16);
+
+        let simulation = CatcherSimulation::calculate(&difficulty, &map).unwrap();
+        let objects = CatchPalpableObject::calculate(&difficulty, &map).unwrap();
+
+        assert_eq!(simulation.steps.len(), objects.len());
+
+        for (step, prev) in simulation.steps.iter().skip(1).zip(objects.iter()) {
+            if step.movement == CatcherMovement::HyperDash {
+                assert!(prev.hyper_dash);
+            }
+
+            if step.walkable() {
+                assert!(step.required_speed() <= simulation.walk_speed());
+            }
+        }
+
+        assert!(simulation.requires_hyper_dash().count() > 0);
+        assert_eq!(simulation.impossible().count(), 0);
+    }
+
+    #[test]
+    fn clock_rate_only_affects_durations() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let nomod = CatcherSimulation::calculate(&Difficulty::new(), &map).unwrap();
+        let dt = CatcherSimulation::calculate(&Difficulty::new().mods(64), &map).unwrap();
+
+        for (a, b) in nomod.steps.iter().zip(dt.steps.iter()) {
+            assert_eq!(a.movement, b.movement);
+            assert!((a.available_time - 1.5 * b.available_time).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn tight_jumps_are_impossible() {
+        let objects = [
+            CatchPalpableObject {
+                kind: CatchPalpableKind::Fruit,
+                start_time: 1000.0,
+                x: 256.0,
+                hyper_dash: false,
+                dist_to_hyper_dash: 0.0,
+                edge_dash: false,
+            },
+            CatchPalpableObject {
+                kind: CatchPalpableKind::Fruit,
+                start_time: 1100.0,
+                x: 500.0,
+                hyper_dash: false,
+                dist_to_hyper_dash: 0.0,
+                edge_dash: false,
+            },
+        ];
+
+        let simulation = CatcherSimulation::new(&objects, 100.0, 1.0);
+
+        assert_eq!(simulation.steps[0].movement, CatcherMovement::Walk);
+        assert!(simulation.steps[0].required_dist.abs() < f64::EPSILON);
+        assert_eq!(simulation.steps[1].movement, CatcherMovement::Impossible);
+        assert!((simulation.steps[1].required_dist - 144.0).abs() < 1e-9);
+        assert_eq!(simulation.impossible().collect::<Vec<_>>(), [1]);
+    }
+}
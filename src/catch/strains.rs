@@ -1,7 +1,10 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::Difficulty, catch::difficulty::DifficultyValues, model::mode::ConvertError, Beatmap,
+    any::{sections, Difficulty, HardSection, StrainTimeline},
+    catch::difficulty::{start_times, DifficultyValues},
+    model::mode::ConvertError,
+    Beatmap,
 };
 
 /// The result of calculating the strains on a osu!catch map.
@@ -16,6 +19,20 @@ pub struct CatchStrains {
 impl CatchStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 750.0;
+
+    /// The timing of the strain sections of the given map.
+    pub fn timeline(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+    ) -> Result<StrainTimeline, ConvertError> {
+        let map = map.convert_ref(GameMode::Catch, difficulty.get_mods())?;
+
+        Ok(StrainTimeline::new(
+            start_times(difficulty, &map).get(1).copied(),
+            Self::SECTION_LEN,
+            difficulty.get_clock_rate(),
+        ))
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<CatchStrains, ConvertError> {
@@ -26,3 +43,122 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<CatchStrains, C
         movement: movement.get_curr_strain_peaks().into_vec(),
     })
 }
+
+/// The strain value of a single fruit or droplet of an osu!catch map.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CatchObjectStrain {
+    /// Index of the object among all fruits and droplets of the map, sorted
+    /// by start time.
+    pub idx: usize,
+    /// Start time of the object in ms, unaffected by the clock rate.
+    pub start_time: f64,
+    /// Strain value of the movement skill.
+    pub movement: f64,
+}
+
+pub fn object_strains(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<Vec<CatchObjectStrain>, ConvertError> {
+    let map = map.convert_ref(GameMode::Catch, difficulty.get_mods())?;
+    let (DifficultyValues { movement, .. }, start_times) =
+        DifficultyValues::calculate_object_strains(difficulty, &map);
+
+    // The first object has no difficulty object
+    let object_strains = start_times
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .zip(movement.into_object_strains())
+        .map(|((idx, start_time), movement)| CatchObjectStrain {
+            idx,
+            start_time,
+            movement,
+        })
+        .collect();
+
+    Ok(object_strains)
+}
+
+/// The hardest sections of a osu!catch map for each skill, sorted by their
+/// peak strain in descending order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatchHardestSections {
+    /// Hardest sections of the movement skill.
+    pub movement: Vec<HardSection>,
+}
+
+pub fn hardest_sections(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    count: usize,
+) -> Result<CatchHardestSections, ConvertError> {
+    let object_strains = object_strains(difficulty, map)?;
+
+    let timeline = StrainTimeline::new(
+        object_strains.first().map(|s| s.start_time),
+        CatchStrains::SECTION_LEN,
+        difficulty.get_clock_rate(),
+    );
+
+    Ok(CatchHardestSections {
+        movement: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.movement)),
+            count,
+        ),
+    })
+}
+
+impl CatchObjectStrain {
+    /// Perform a difficulty calculation and return the strain values of each
+    /// fruit and droplet.
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Vec<Self>, ConvertError> {
+        object_strains(difficulty, map)
+    }
+}
+
+impl CatchHardestSections {
+    /// Perform a difficulty calculation and return the `count` hardest
+    /// sections for each skill.
+    pub fn calculate(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        count: usize,
+    ) -> Result<Self, ConvertError> {
+        hardest_sections(difficulty, map, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_strains_respect_passed_objects() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let all = object_strains(&Difficulty::new(), &map).unwrap();
+        let partial = object_strains(&Difficulty::new().passed_objects(100), &map).unwrap();
+
+        assert_eq!(partial.len(), 99);
+        assert_eq!(all[..99], partial[..]);
+        assert!(all.windows(2).all(|w| w[0].idx + 1 == w[1].idx));
+        assert!(all.windows(2).all(|w| w[0].start_time <= w[1].start_time));
+    }
+
+    #[test]
+    fn object_strains_are_opt_in() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let difficulty = Difficulty::new();
+
+        let DifficultyValues { movement, .. } = DifficultyValues::calculate(&difficulty, &map);
+        assert!(movement.into_object_strains().is_empty());
+
+        let (DifficultyValues { movement, .. }, _) =
+            DifficultyValues::calculate_object_strains(&difficulty, &map);
+        assert!(!movement.into_object_strains().is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{hit_object::HitObjectKind, mode::ConvertError},
+    Beatmap, Difficulty,
+};
+
+use super::{
+    difficulty::start_times,
+    object::{ManiaObject, ObjectParams},
+    strains::object_strains,
+};
+
+/// Maximum time in ms between two notes of the same column to be considered
+/// a jack, unaffected by the clock rate.
+const JACK_MAX_INTERVAL: f64 = 150.0;
+/// Maximum difference in ms between start times of notes in the same chord.
+const CHORD_MAX_INTERVAL: f64 = 1.0;
+
+/// Metrics of a single column of an osu!mania map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManiaColumn {
+    /// The amount of notes in the column, including hold notes.
+    pub n_notes: usize,
+    /// The amount of hold notes in the column.
+    pub n_hold_notes: usize,
+    /// The amount of notes that directly follow a note of the same column as
+    /// part of a jack.
+    pub n_jack_notes: usize,
+    /// The highest strain value of a note in the column.
+    pub peak_strain: f64,
+}
+
+/// Consecutive notes in the same column in quick succession.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManiaJack {
+    /// The column of the jack.
+    pub column: usize,
+    /// Index of the first hit object of the jack.
+    pub first_object: usize,
+    /// Index of the last hit object of the jack.
+    pub last_object: usize,
+    /// Start time of the first hit object in map time in ms.
+    pub start_time: f64,
+    /// Start time of the last hit object in map time in ms.
+    pub end_time: f64,
+    /// The amount of notes in the jack.
+    pub n_notes: usize,
+}
+
+/// Column, long note, chord, and jack metrics of an osu!mania map.
+///
+/// [`Difficulty::passed_objects`] and [`Difficulty::passed_time`] are
+/// respected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManiaAnalysis {
+    /// Metrics of each column, sorted from left to right.
+    pub columns: Vec<ManiaColumn>,
+    /// The amount of considered hit objects.
+    pub n_objects: usize,
+    /// The amount of considered hold notes.
+    pub n_hold_notes: usize,
+    /// The amount of chords per chord size, i.e. `chords[0]` is the amount of
+    /// single notes, `chords[1]` the amount of two-note chords, and so on.
+    pub chords: Vec<usize>,
+    /// All jacks, sorted by time.
+    pub jacks: Vec<ManiaJack>,
+    /// Whether the map was converted from osu!standard.
+    pub is_convert: bool,
+}
+
+impl ManiaAnalysis {
+    /// Analyze the columns, long notes, chords, and jacks of a map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::mania::ManiaAnalysis;
+    ///
+    /// let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+    /// let analysis = ManiaAnalysis::calculate(&Difficulty::new(), &map).unwrap();
+    ///
+    /// println!(
+    ///     "{} columns, {:.2}% long notes, {} jacks",
+    ///     analysis.columns.len(),
+    ///     100.0 * analysis.long_note_ratio(),
+    ///     analysis.jacks.len(),
+    /// );
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(&map));
+        let take = difficulty.get_passed_objects().min(map.hit_objects.len());
+
+        let total_columns = map.cs.round_ties_even().max(1.0);
+        let mut params = ObjectParams::new(&map);
+
+        let column_indices: Vec<_> = map
+            .hit_objects
+            .iter()
+            .take(take)
+            .map(|h| ManiaObject::new(h, total_columns, &mut params).column)
+            .collect();
+
+        let mut columns = vec![ManiaColumn::default(); total_columns as usize];
+
+        let mut chords = vec![0; total_columns as usize];
+        let max_chord_size = chords.len();
+        let mut chord: Option<(f64, usize)> = None;
+
+        let mut jacks = Vec::new();
+        // The last note and the currently ongoing jack of each column
+        let mut last_notes: Vec<Option<(usize, f64)>> = vec![None; columns.len()];
+        let mut curr_jacks: Vec<Option<ManiaJack>> = vec![None; columns.len()];
+
+        for ((idx, h), &column_idx) in map.hit_objects.iter().enumerate().zip(&column_indices) {
+            let column = &mut columns[column_idx];
+
+            column.n_notes += 1;
+
+            if !matches!(h.kind, HitObjectKind::Circle) {
+                column.n_hold_notes += 1;
+            }
+
+            match chord {
+                Some((start_time, ref mut size))
+                    if h.start_time - start_time <= CHORD_MAX_INTERVAL =>
+                {
+                    *size += 1;
+                }
+                _ => {
+                    if let Some((_, size)) = chord {
+                        chords[size.min(max_chord_size) - 1] += 1;
+                    }
+
+                    chord = Some((h.start_time, 1));
+                }
+            }
+
+            let jack_start = last_notes[column_idx]
+                .filter(|(_, last_time)| h.start_time - last_time <= JACK_MAX_INTERVAL);
+
+            if let Some((first_object, start_time)) = jack_start {
+                column.n_jack_notes += 1;
+
+                let jack = curr_jacks[column_idx].get_or_insert(ManiaJack {
+                    column: column_idx,
+                    first_object,
+                    last_object: idx,
+                    start_time,
+                    end_time: h.start_time,
+                    n_notes: 1,
+                });
+
+                jack.last_object = idx;
+                jack.end_time = h.start_time;
+                jack.n_notes += 1;
+            } else if let Some(jack) = curr_jacks[column_idx].take() {
+                jacks.push(jack);
+            }
+
+            last_notes[column_idx] = Some((idx, h.start_time));
+        }
+
+        if let Some((_, size)) = chord {
+            chords[size.min(max_chord_size) - 1] += 1;
+        }
+
+        jacks.extend(curr_jacks.into_iter().flatten());
+        jacks.sort_by_key(|jack| jack.first_object);
+
+        for strain in object_strains(difficulty, &map)? {
+            let column = &mut columns[column_indices[strain.idx]];
+            column.peak_strain = column.peak_strain.max(strain.strain);
+        }
+
+        let n_hold_notes = columns.iter().map(|column| column.n_hold_notes).sum();
+
+        Ok(Self {
+            columns,
+            n_objects: take,
+            n_hold_notes,
+            chords,
+            jacks,
+            is_convert: map.is_convert,
+        })
+    }
+
+    /// The fraction of hold notes among all considered hit objects.
+    pub fn long_note_ratio(&self) -> f64 {
+        if self.n_objects == 0 {
+            return 0.0;
+        }
+
+        self.n_hold_notes as f64 / self.n_objects as f64
+    }
+
+    /// The average amount of notes per chord, counting single notes as
+    /// chords of size one.
+    pub fn average_chord_size(&self) -> f64 {
+        let n_chords: usize = self.chords.iter().sum();
+
+        if n_chords == 0 {
+            return 0.0;
+        }
+
+        self.n_objects as f64 / n_chords as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mania::Mania;
+
+    use super::*;
+
+    #[test]
+    fn columns_add_up() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let analysis = ManiaAnalysis::calculate(&Difficulty::new(), &map).unwrap();
+        let attrs = Difficulty::new().calculate_for_mode::<Mania>(&map).unwrap();
+
+        assert_eq!(analysis.n_objects, attrs.n_objects as usize);
+        assert_eq!(analysis.n_hold_notes, attrs.n_hold_notes as usize);
+
+        let notes: usize = analysis.columns.iter().map(|column| column.n_notes).sum();
+        assert_eq!(notes, analysis.n_objects);
+
+        let chord_notes: usize = analysis
+            .chords
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (i + 1) * count)
+            .sum();
+        assert_eq!(chord_notes, analysis.n_objects);
+
+        let jack_notes: usize = analysis.columns.iter().map(|c| c.n_jack_notes).sum();
+        let jack_len: usize = analysis.jacks.iter().map(|jack| jack.n_notes - 1).sum();
+        assert_eq!(jack_notes, jack_len);
+
+        assert!(analysis
+            .columns
+            .iter()
+            .all(|column| column.peak_strain > 0.0));
+    }
+
+    #[test]
+    fn converts_and_respects_passed_objects() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new().passed_objects(100);
+        let analysis = ManiaAnalysis::calculate(&difficulty, &map).unwrap();
+
+        assert!(analysis.is_convert);
+        assert_eq!(analysis.n_objects, 100);
+        assert!((0.0..=1.0).contains(&analysis.long_note_ratio()));
+    }
+}
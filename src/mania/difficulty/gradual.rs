@@ -6,6 +6,7 @@ use crate::{
     any::difficulty::skills::Skill,
     mania::object::ObjectParams,
     model::{beatmap::HitWindows, hit_object::HitObject, mode::ConvertError},
+    util::checkpoints::Checkpoints,
     Beatmap, Difficulty,
 };
 
@@ -50,14 +51,21 @@ pub struct ManiaGradualDifficulty {
     pub(crate) idx: usize,
     pub(crate) difficulty: Difficulty,
     objects_is_circle: Box<[bool]>,
+    start_times: Box<[f64]>,
     is_convert: bool,
     strain: Strain,
     diff_objects: Box<[ManiaDifficultyObject]>,
     hit_window: f64,
     note_state: NoteState,
+    checkpoints: Checkpoints<Snapshot>,
 }
 
-#[derive(Default)]
+struct Snapshot {
+    strain: Strain,
+    note_state: NoteState,
+}
+
+#[derive(Clone, Default)]
 struct NoteState {
     curr_combo: u32,
     n_hold_notes: u32,
@@ -65,10 +73,20 @@ struct NoteState {
 
 impl ManiaGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!mania maps.
+    ///
+    /// Objects past [`Difficulty::passed_objects`] or [`Difficulty::passed_time`]
+    /// are not processed.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
         let map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
 
-        let take = difficulty.get_passed_objects();
+        let mut start_times: Vec<f64> = super::start_times(&map).collect();
+
+        let take = difficulty
+            .resolve_passed_time(|| start_times.iter().copied())
+            .get_passed_objects();
+
+        start_times.truncate(take);
+
         let total_columns = map.cs.round_ties_even().max(1.0);
         let clock_rate = difficulty.get_clock_rate();
         let mut params = ObjectParams::new(&map);
@@ -104,17 +122,130 @@ impl ManiaGradualDifficulty {
             );
         }
 
+        let checkpoints = Checkpoints::new(Snapshot {
+            strain: strain.clone(),
+            note_state: note_state.clone(),
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
             objects_is_circle,
+            start_times: start_times.into_boxed_slice(),
             is_convert: map.is_convert,
             strain,
             diff_objects,
             hit_window,
             note_state,
+            checkpoints,
         })
     }
+
+    /// Store a snapshot of the current state every `interval` hit objects.
+    ///
+    /// Snapshots allow [`seek`] to go backwards without processing all
+    /// previous hit objects again. Smaller intervals make seeking faster but
+    /// require more memory. An interval of `0` disables snapshots.
+    ///
+    /// [`seek`]: ManiaGradualDifficulty::seek
+    #[must_use]
+    pub const fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the state after processing the first `idx` hit objects and
+    /// return the resulting attributes.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Values of `idx` larger than the amount of
+    /// hit objects will process all hit objects.
+    ///
+    /// Returns `None` if `idx` is `0` or the map has no hit objects.
+    ///
+    /// [`checkpoint_interval`]: ManiaGradualDifficulty::checkpoint_interval
+    pub fn seek(&mut self, idx: usize) -> Option<ManiaDifficultyAttributes> {
+        let Some(n) = idx.checked_sub(1) else {
+            self.restore(0);
+
+            return None;
+        };
+
+        if n < self.idx {
+            self.restore(n);
+        }
+
+        self.nth(n - self.idx)
+    }
+
+    /// Process all hit objects that started within the given amount of
+    /// milliseconds and return the resulting attributes.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further hit object started within that time.
+    pub fn passed_time(&mut self, passed_time: f64) -> Option<ManiaDifficultyAttributes> {
+        self.remaining_until(passed_time)
+            .checked_sub(1)
+            .and_then(|n| self.nth(n))
+    }
+
+    /// Amount of hit objects that remain to be processed until the given
+    /// amount of milliseconds passed.
+    pub(crate) fn remaining_until(&self, passed_time: f64) -> usize {
+        self.difficulty
+            .count_passed(&self.start_times, passed_time)
+            .saturating_sub(self.idx)
+    }
+
+    fn restore(&mut self, idx: usize) {
+        let (idx, snapshot) = self.checkpoints.latest_until(idx);
+
+        self.idx = idx;
+        self.strain = snapshot.strain.clone();
+        self.note_state = snapshot.note_state.clone();
+    }
+
+    fn record_checkpoint(&mut self) {
+        let Self {
+            idx,
+            strain,
+            note_state,
+            checkpoints,
+            ..
+        } = self;
+
+        checkpoints.record(*idx, || Snapshot {
+            strain: strain.clone(),
+            note_state: note_state.clone(),
+        });
+    }
+
+    /// Process the next `take` hit objects without evaluating them.
+    fn advance(&mut self, mut take: usize) {
+        let skip_iter = self
+            .diff_objects
+            .iter()
+            .zip(self.objects_is_circle.iter().skip(1))
+            .skip(self.idx.saturating_sub(1));
+
+        // The first note has no difficulty object
+        if self.idx == 0 && take > 0 {
+            take -= 1;
+            self.idx += 1;
+        }
+
+        let mut strain = Skill::new(&mut self.strain, &self.diff_objects);
+        let clock_rate = self.difficulty.get_clock_rate();
+
+        for (curr, is_circle) in skip_iter.take(take) {
+            increment_combo(*is_circle, curr, &mut self.note_state, clock_rate);
+            strain.process(curr);
+            self.idx += 1;
+        }
+    }
 }
 
 impl Iterator for ManiaGradualDifficulty {
@@ -136,11 +267,12 @@ impl Iterator for ManiaGradualDifficulty {
                 &mut self.note_state,
                 self.difficulty.get_clock_rate(),
             );
-        } else if self.objects_is_circle.is_empty() {
+        } else if self.start_times.is_empty() {
             return None;
         }
 
         self.idx += 1;
+        self.record_checkpoint();
 
         Some(ManiaDifficultyAttributes {
             stars: self.strain.as_difficulty_value() * DIFFICULTY_MULTIPLIER,
@@ -159,27 +291,13 @@ impl Iterator for ManiaGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self
-            .diff_objects
-            .iter()
-            .zip(self.objects_is_circle.iter().skip(1))
-            .skip(self.idx.saturating_sub(1));
-
         let mut take = cmp::min(n, self.len().saturating_sub(1));
 
-        // The first note has no difficulty object
-        if self.idx == 0 && take > 0 {
-            take -= 1;
-            self.idx += 1;
-        }
-
-        let mut strain = Skill::new(&mut self.strain, &self.diff_objects);
-        let clock_rate = self.difficulty.get_clock_rate();
-
-        for (curr, is_circle) in skip_iter.take(take) {
-            increment_combo(*is_circle, curr, &mut self.note_state, clock_rate);
-            strain.process(curr);
-            self.idx += 1;
+        while take > 0 {
+            let step = cmp::min(take, self.checkpoints.steps_until_due(self.idx));
+            self.advance(step);
+            self.record_checkpoint();
+            take -= step;
         }
 
         self.next()
@@ -188,7 +306,8 @@ impl Iterator for ManiaGradualDifficulty {
 
 impl ExactSizeIterator for ManiaGradualDifficulty {
     fn len(&self) -> usize {
-        self.diff_objects.len() + 1 - self.idx
+        // Only contains the start times of objects that will be processed
+        self.start_times.len() - self.idx
     }
 }
 
@@ -267,4 +386,55 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn stops_at_difficulty_passed_time() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64).passed_time(12345.0);
+        let gradual = ManiaGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        let expected = difficulty.calculate_for_mode::<Mania>(&map).unwrap();
+
+        assert_eq!(gradual.last(), Some(expected));
+    }
+
+    #[test]
+    fn passed_time_caps_remaining_objects() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new().passed_time(12345.0);
+        let gradual = ManiaGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.remaining_until(f64::MAX), gradual.len());
+
+        // Nothing passed before the first object
+        let difficulty = Difficulty::new().passed_time(-1.0);
+        let mut gradual = ManiaGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.next(), None);
+    }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new();
+        let mut gradual = ManiaGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .checkpoint_interval(50);
+
+        let len = gradual.len();
+
+        for idx in [len / 2, 10, len, 1, 2, 137, 0, 99, 100] {
+            let expected = idx.checked_sub(1).and_then(|n| {
+                ManiaGradualDifficulty::new(difficulty.clone(), &map)
+                    .unwrap()
+                    .nth(n)
+            });
+
+            assert_eq!(gradual.seek(idx), expected, "idx={idx}");
+        }
+    }
 }
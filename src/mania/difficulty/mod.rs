@@ -25,6 +25,7 @@ pub fn difficulty(
     map: &Beatmap,
 ) -> Result<ManiaDifficultyAttributes, ConvertError> {
     let map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
+    let difficulty = &*difficulty.resolve_passed_time(|| start_times(&map));
 
     let n_objects = cmp::min(difficulty.get_passed_objects(), map.hit_objects.len()) as u32;
 
@@ -46,6 +47,11 @@ pub fn difficulty(
     })
 }
 
+/// Start times of all objects that count towards the amount of passed objects.
+pub(crate) fn start_times(map: &Beatmap) -> impl Iterator<Item = f64> + '_ {
+    map.hit_objects.iter().map(|h| h.start_time)
+}
+
 pub struct DifficultyValues {
     pub strain: Strain,
     pub max_combo: u32,
@@ -54,6 +60,17 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, map, false)
+    }
+
+    /// Same as [`DifficultyValues::calculate`] but the strain skill also
+    /// stores the strain value of each object.
+    pub fn calculate_object_strains(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, map, true)
+    }
+
+    fn calculate_with(difficulty: &Difficulty, map: &Beatmap, object_strains: bool) -> Self {
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(map));
         let take = difficulty.get_passed_objects();
         let total_columns = map.cs.round_ties_even().max(1.0);
         let clock_rate = difficulty.get_clock_rate();
@@ -69,6 +86,10 @@ impl DifficultyValues {
 
         let mut strain = Strain::new(total_columns as usize);
 
+        if object_strains {
+            strain.enable_object_strains();
+        }
+
         {
             let mut strain = Skill::new(&mut strain, &diff_objects);
 
@@ -14,6 +14,7 @@ const RELEASE_THRESHOLD: f64 = 30.0;
 const SKILL_MULTIPLIER: f64 = 1.0;
 const STRAIN_DECAY_BASE: f64 = 1.0;
 
+#[derive(Clone)]
 #[allow(clippy::struct_field_names)]
 pub struct Strain {
     start_times: Box<[f64]>,
@@ -42,6 +43,14 @@ impl Strain {
         self.inner.get_curr_strain_peaks()
     }
 
+    pub fn enable_object_strains(&mut self) {
+        self.inner.enable_object_strains();
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.into_object_strains()
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
@@ -189,6 +198,8 @@ impl Skill<'_, Strain> {
         }
 
         let strain_value_at = self.inner.strain_value_at(curr);
+
+        self.inner.inner.save_object_strain(strain_value_at);
         *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
     }
 }
@@ -9,16 +9,26 @@ use crate::{
 };
 
 pub use self::{
+    analysis::{ManiaAnalysis, ManiaColumn, ManiaJack},
     attributes::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
     difficulty::gradual::ManiaGradualDifficulty,
-    performance::{gradual::ManiaGradualPerformance, ManiaPerformance},
+    note::{ManiaNote, ManiaNotes},
+    performance::{
+        gradual::ManiaGradualPerformance,
+        judgement::{ManiaJudgement, ManiaJudgementError, ManiaJudgementPerformance},
+        ManiaPerformance,
+    },
     score_state::ManiaScoreState,
-    strains::ManiaStrains,
+    strains::{ManiaHardestSections, ManiaObjectStrain, ManiaStrains},
 };
 
+pub(crate) use self::object::statistics;
+
+mod analysis;
 mod attributes;
 mod convert;
 mod difficulty;
+mod note;
 mod object;
 mod performance;
 mod score_state;
@@ -0,0 +1,132 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::{
+    difficulty::start_times,
+    object::{ManiaObject, ObjectParams},
+};
+
+/// A hit object of an osu!mania map as it is played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManiaNote {
+    /// The column of the note, starting at `0` for the leftmost column.
+    pub column: usize,
+    /// Start time of the note in map time in ms.
+    pub start_time: f64,
+    /// End time of the note in map time in ms.
+    ///
+    /// Only differs from the start time for hold notes.
+    pub end_time: f64,
+    /// Whether the note is a hold note.
+    pub hold: bool,
+}
+
+/// The notes of an osu!mania map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManiaNotes {
+    /// The amount of columns, i.e. keys.
+    pub total_columns: usize,
+    /// All notes, sorted by their start time.
+    pub notes: Vec<ManiaNote>,
+    /// Whether the map was converted from osu!standard.
+    pub is_convert: bool,
+}
+
+impl ManiaNotes {
+    /// Convert the hit objects of a map into osu!mania notes.
+    ///
+    /// osu!standard maps are converted with respect to the key mods.
+    /// [`Difficulty::passed_objects`] and [`Difficulty::passed_time`] are
+    /// respected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::mania::ManiaNotes;
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// // 4K
+    /// let converted = ManiaNotes::calculate(&Difficulty::new().mods(32_768), &map).unwrap();
+    /// assert_eq!(converted.total_columns, 4);
+    ///
+    /// for note in converted.notes.iter().filter(|note| note.hold) {
+    ///     println!("Hold note in column {} at {}ms", note.column, note.start_time);
+    /// }
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(&map));
+        let take = difficulty.get_passed_objects();
+
+        let total_columns = map.cs.round_ties_even().max(1.0);
+        let mut params = ObjectParams::new(&map);
+
+        let notes = map
+            .hit_objects
+            .iter()
+            .take(take)
+            .map(|h| {
+                let ManiaObject {
+                    start_time,
+                    end_time,
+                    column,
+                } = ManiaObject::new(h, total_columns, &mut params);
+
+                ManiaNote {
+                    column,
+                    start_time,
+                    end_time,
+                    hold: !h.is_circle(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            total_columns: total_columns as usize,
+            notes,
+            is_convert: map.is_convert,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mania::Mania;
+
+    use super::*;
+
+    #[test]
+    fn notes_match_attributes() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let notes = ManiaNotes::calculate(&Difficulty::new(), &map).unwrap();
+
+        let attrs = Difficulty::new().calculate_for_mode::<Mania>(&map).unwrap();
+
+        let n_holds = notes.notes.iter().filter(|note| note.hold).count();
+
+        assert!(!notes.is_convert);
+        assert_eq!(notes.notes.len(), attrs.n_objects as usize);
+        assert_eq!(n_holds, attrs.n_hold_notes as usize);
+        assert!(notes
+            .notes
+            .iter()
+            .all(|note| note.column < notes.total_columns));
+    }
+
+    #[test]
+    fn key_mods_change_columns() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for (mods, keys) in [(0, 7), (16, 7), (32_768, 4), (262_144, 7), (67_108_864, 1)] {
+            let notes = ManiaNotes::calculate(&Difficulty::new().mods(mods), &map).unwrap();
+
+            assert!(notes.is_convert);
+            assert_eq!(notes.total_columns, keys);
+            assert!(notes.notes.iter().all(|note| note.column < keys));
+            assert!(notes.notes.iter().any(|note| note.column == keys - 1));
+        }
+    }
+}
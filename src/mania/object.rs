@@ -1,11 +1,27 @@
 use rosu_map::section::{general::GameMode, hit_objects::CurveBuffers};
 
 use crate::model::{
-    beatmap::Beatmap,
+    beatmap::{Beatmap, BeatmapStatistics},
     control_point::{DifficultyPoint, TimingPoint},
     hit_object::{HitObject, HitObjectKind, HoldNote, Spinner},
 };
 
+pub fn statistics(map: &Beatmap, stats: &mut BeatmapStatistics) {
+    let mut params = ObjectParams::new(map);
+
+    for h in map.hit_objects.iter() {
+        let h = ManiaObject::new(h, map.cs, &mut params);
+        stats.last_object_time = stats.last_object_time.max(h.end_time);
+    }
+
+    let n_objects = map.hit_objects.len() as u32;
+
+    stats.n_sliders = params.n_hold_notes();
+    stats.n_circles = n_objects - stats.n_sliders;
+    stats.max_combo = params.max_combo();
+    stats.n_slider_ticks = stats.max_combo - n_objects;
+}
+
 pub struct ManiaObject {
     pub start_time: f64,
     pub end_time: f64,
@@ -0,0 +1,155 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::{gradual::ManiaGradualPerformance, ManiaPerformanceAttributes, ManiaScoreState};
+
+/// A single judgement of a note or hold note.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ManiaJudgement {
+    /// The hit object was hit with a 320.
+    Perfect,
+    /// The hit object was hit with a 300.
+    Great,
+    /// The hit object was hit with a 200.
+    Good,
+    /// The hit object was hit with a 100.
+    Ok,
+    /// The hit object was hit with a 50.
+    Meh,
+    /// The hit object was missed.
+    Miss,
+}
+
+/// Error type when a [`ManiaJudgement`] cannot be processed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ManiaJudgementError {
+    /// All hit objects have already been judged
+    NoRemainingObjects,
+}
+
+impl Error for ManiaJudgementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for ManiaJudgementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ManiaJudgementError::NoRemainingObjects => {
+                f.write_str("All hit objects have already been judged")
+            }
+        }
+    }
+}
+
+/// Gradually calculate the performance attributes of an osu!mania map
+/// based on individual judgements.
+///
+/// Unlike [`ManiaGradualPerformance`], this type keeps track of the score
+/// state itself. Each hit object, including hold notes, receives a single
+/// judgement.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::mania::{ManiaJudgement, ManiaJudgementPerformance};
+///
+/// let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+///
+/// let difficulty = Difficulty::new().mods(64); // DT
+/// let mut gradual = ManiaJudgementPerformance::new(difficulty, &map).unwrap();
+///
+/// for judgement in [ManiaJudgement::Perfect, ManiaJudgement::Good, ManiaJudgement::Miss] {
+///     let attrs = gradual.judge(judgement).unwrap();
+///     println!("PP: {}", attrs.pp);
+/// }
+///
+/// assert_eq!(gradual.state().n200, 1);
+/// ```
+pub struct ManiaJudgementPerformance {
+    gradual: ManiaGradualPerformance,
+    state: ManiaScoreState,
+}
+
+impl ManiaJudgementPerformance {
+    /// Create a new judgement-based performance calculator for osu!mania
+    /// maps.
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let gradual = ManiaGradualPerformance::new(difficulty, map)?;
+
+        Ok(Self {
+            gradual,
+            state: ManiaScoreState::new(),
+        })
+    }
+
+    /// Process the judgement of the next hit object and calculate the
+    /// performance attributes for the resulting score state.
+    pub fn judge(
+        &mut self,
+        judgement: ManiaJudgement,
+    ) -> Result<ManiaPerformanceAttributes, ManiaJudgementError> {
+        if self.gradual.len() == 0 {
+            return Err(ManiaJudgementError::NoRemainingObjects);
+        }
+
+        match judgement {
+            ManiaJudgement::Perfect => self.state.n320 += 1,
+            ManiaJudgement::Great => self.state.n300 += 1,
+            ManiaJudgement::Good => self.state.n200 += 1,
+            ManiaJudgement::Ok => self.state.n100 += 1,
+            ManiaJudgement::Meh => self.state.n50 += 1,
+            ManiaJudgement::Miss => self.state.misses += 1,
+        }
+
+        self.gradual
+            .next(self.state.clone())
+            .ok_or(ManiaJudgementError::NoRemainingObjects)
+    }
+
+    /// The current score state.
+    pub const fn state(&self) -> &ManiaScoreState {
+        &self.state
+    }
+
+    /// Returns the amount of remaining objects.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.gradual.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mania::ManiaPerformance;
+
+    use super::*;
+
+    #[test]
+    fn full_combo_matches_ss() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let mut gradual = ManiaJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        let mut last = None;
+
+        while gradual.len() > 0 {
+            last = Some(gradual.judge(ManiaJudgement::Perfect).unwrap());
+        }
+
+        let expected = ManiaPerformance::from(&map).calculate().unwrap();
+
+        assert_eq!(last.unwrap(), expected);
+        assert_eq!(
+            gradual.judge(ManiaJudgement::Perfect),
+            Err(ManiaJudgementError::NoRemainingObjects)
+        );
+    }
+}
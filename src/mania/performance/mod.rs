@@ -4,7 +4,11 @@ use rosu_map::section::general::GameMode;
 
 use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
-    model::{mode::ConvertError, mods::GameMods},
+    model::{
+        limits::{CalcLimits, LimitError},
+        mode::ConvertError,
+        mods::GameMods,
+    },
     osu::OsuPerformance,
     util::map_or_attrs::MapOrAttrs,
     Performance,
@@ -17,6 +21,7 @@ use super::{
 };
 
 pub mod gradual;
+pub mod judgement;
 
 /// Performance calculator on osu!mania maps.
 #[derive(Clone, Debug, PartialEq)]
@@ -109,6 +114,20 @@ impl<'map> ManiaPerformance<'map> {
         self
     }
 
+    /// Amount of passed time in milliseconds for partial plays, e.g. a fail.
+    ///
+    /// Only takes effect if the difficulty attributes still have to be
+    /// calculated, i.e. if a beatmap was provided instead of attributes.
+    ///
+    /// See [`Difficulty::passed_time`] for more information.
+    ///
+    /// [`Difficulty::passed_time`]: crate::Difficulty::passed_time
+    pub fn passed_time(mut self, passed_time: f64) -> Self {
+        self.difficulty = self.difficulty.passed_time(passed_time);
+
+        self
+    }
+
     /// Adjust the clock rate used in the calculation.
     ///
     /// If none is specified, it will take the clock rate based on the mods
@@ -817,6 +836,10 @@ impl<'map> ManiaPerformance<'map> {
             hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
+
+    pub(crate) fn check_limits(&self, limits: &CalcLimits) -> Result<(), LimitError> {
+        self.map_or_attrs.check_limits(&self.difficulty, limits)
+    }
 }
 
 impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
@@ -1,7 +1,10 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::Difficulty, mania::difficulty::DifficultyValues, model::mode::ConvertError, Beatmap,
+    any::{sections, Difficulty, HardSection, StrainTimeline},
+    mania::difficulty::DifficultyValues,
+    model::mode::ConvertError,
+    Beatmap,
 };
 
 /// The result of calculating the strains on a osu!mania map.
@@ -16,6 +19,20 @@ pub struct ManiaStrains {
 impl ManiaStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 400.0;
+
+    /// The timing of the strain sections of the given map.
+    pub fn timeline(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+    ) -> Result<StrainTimeline, ConvertError> {
+        let map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
+
+        Ok(StrainTimeline::new(
+            map.hit_objects.get(1).map(|h| h.start_time),
+            Self::SECTION_LEN,
+            difficulty.get_clock_rate(),
+        ))
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<ManiaStrains, ConvertError> {
@@ -26,3 +43,89 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<ManiaStrains, C
         strains: values.strain.get_curr_strain_peaks().into_vec(),
     })
 }
+
+/// The strain value of a single hit object of an osu!mania map.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ManiaObjectStrain {
+    /// Index of the hit object in the map.
+    pub idx: usize,
+    /// Start time of the hit object in ms, unaffected by the clock rate.
+    pub start_time: f64,
+    /// Strain value of the strain skill.
+    pub strain: f64,
+}
+
+pub fn object_strains(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<Vec<ManiaObjectStrain>, ConvertError> {
+    let map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
+    let values = DifficultyValues::calculate_object_strains(difficulty, &map);
+
+    // The first hit object has no difficulty object
+    let object_strains = map
+        .hit_objects
+        .iter()
+        .enumerate()
+        .skip(1)
+        .zip(values.strain.into_object_strains())
+        .map(|((idx, h), strain)| ManiaObjectStrain {
+            idx,
+            start_time: h.start_time,
+            strain,
+        })
+        .collect();
+
+    Ok(object_strains)
+}
+
+/// The hardest sections of a osu!mania map for each skill, sorted by their
+/// peak strain in descending order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManiaHardestSections {
+    /// Hardest sections of the strain skill.
+    pub strain: Vec<HardSection>,
+}
+
+pub fn hardest_sections(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    count: usize,
+) -> Result<ManiaHardestSections, ConvertError> {
+    let object_strains = object_strains(difficulty, map)?;
+
+    let timeline = StrainTimeline::new(
+        object_strains.first().map(|s| s.start_time),
+        ManiaStrains::SECTION_LEN,
+        difficulty.get_clock_rate(),
+    );
+
+    Ok(ManiaHardestSections {
+        strain: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.strain)),
+            count,
+        ),
+    })
+}
+impl ManiaObjectStrain {
+    /// Perform a difficulty calculation and return the strain values of each
+    /// note.
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Vec<Self>, ConvertError> {
+        object_strains(difficulty, map)
+    }
+}
+
+impl ManiaHardestSections {
+    /// Perform a difficulty calculation and return the `count` hardest
+    /// sections for each skill.
+    pub fn calculate(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        count: usize,
+    ) -> Result<Self, ConvertError> {
+        hardest_sections(difficulty, map, count)
+    }
+}
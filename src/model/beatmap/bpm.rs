@@ -3,42 +3,125 @@ use std::collections::HashMap;
 use crate::model::{control_point::TimingPoint, hit_object::HitObject};
 
 pub fn bpm(last_hit_object: Option<&HitObject>, timing_points: &[TimingPoint]) -> f64 {
-    // This is incorrect if the last object is a slider since there
-    // is no reasonable way to get the slider end time at this point.
-    let last_time = last_hit_object
-        .map(HitObject::end_time)
-        .or_else(|| timing_points.last().map(|t| t.time))
-        .unwrap_or(0.0);
-
-    let mut bpm_points = BeatLenDuration::new(last_time);
-
-    // * osu-stable forced the first control point to start at 0.
-    // * This is reproduced here to maintain compatibility around
-    // * osu!mania scroll speed and song select display.
-    match timing_points {
-        [curr] => bpm_points.add(curr.beat_len, 0.0, last_time),
-        [curr, next, ..] => bpm_points.add(curr.beat_len, 0.0, next.time),
-        [] => {}
-    }
+    BpmAnalysis::new(last_hit_object, timing_points, 1.0).dominant
+}
+
+/// BPM information of a [`Beatmap`].
+///
+/// All values are adjusted by the clock rate.
+///
+/// [`Beatmap`]: super::Beatmap
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BpmAnalysis {
+    /// The lowest BPM of all segments that last at least one second.
+    pub min: f64,
+    /// The highest BPM of all segments that last at least one second.
+    pub max: f64,
+    /// The BPM that is active for the longest total duration.
+    pub dominant: f64,
+    /// Consecutive sections of constant BPM up until the last hit object.
+    pub segments: Vec<BpmSegment>,
+}
 
-    timing_points
-        .iter()
-        .skip(1)
-        .zip(timing_points.iter().skip(2).map(|t| t.time))
-        .for_each(|(curr, next_time)| bpm_points.add(curr.beat_len, curr.time, next_time));
+/// A section of a [`Beatmap`] with constant BPM.
+///
+/// [`Beatmap`]: super::Beatmap
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BpmSegment {
+    /// The BPM of the segment.
+    pub bpm: f64,
+    /// Start time of the segment in ms.
+    pub start_time: f64,
+    /// End time of the segment in ms.
+    pub end_time: f64,
+}
 
-    if let [.., _, curr] = timing_points {
-        bpm_points.add(curr.beat_len, curr.time, last_time);
+impl BpmSegment {
+    /// The duration of the segment in ms.
+    pub fn duration(&self) -> f64 {
+        self.end_time - self.start_time
     }
+}
+
+impl BpmAnalysis {
+    /// Segments that are shorter in map time are ignored for [`Self::min`]
+    /// and [`Self::max`] so that gimmick timing points don't affect them.
+    const MIN_SEGMENT_DURATION: f64 = 1000.0;
+
+    pub(crate) fn new(
+        last_hit_object: Option<&HitObject>,
+        timing_points: &[TimingPoint],
+        clock_rate: f64,
+    ) -> Self {
+        // This is incorrect if the last object is a slider since there
+        // is no reasonable way to get the slider end time at this point.
+        let last_time = last_hit_object
+            .map(HitObject::end_time)
+            .or_else(|| timing_points.last().map(|t| t.time))
+            .unwrap_or(0.0);
+
+        let mut bpm_points = BeatLenDuration::new(last_time);
+        let mut segments: Vec<BpmSegment> = Vec::with_capacity(timing_points.len());
+
+        for (i, curr) in timing_points.iter().enumerate() {
+            // * osu-stable forced the first control point to start at 0.
+            // * This is reproduced here to maintain compatibility around
+            // * osu!mania scroll speed and song select display.
+            let start_time = if i == 0 { 0.0 } else { curr.time };
+
+            let next_time = timing_points.get(i + 1).map_or(last_time, |next| next.time);
+
+            let beat_len = round_beat_len(curr.beat_len);
+            bpm_points.add(beat_len, start_time, next_time);
 
-    let most_common_beat_len = bpm_points
-        .map
-        .into_iter()
-        // * Get the most common one, or 0 as a suitable default
-        .max_by(|(_, a), (_, b)| a.total_cmp(b))
-        .map_or(0.0, |(beat_len, _)| f64::from_bits(beat_len));
+            if start_time > last_time {
+                break;
+            }
 
-    60_000.0 / most_common_beat_len
+            let segment = BpmSegment {
+                bpm: 60_000.0 / beat_len * clock_rate,
+                start_time: start_time / clock_rate,
+                end_time: next_time.min(last_time) / clock_rate,
+            };
+
+            match segments.last_mut() {
+                Some(prev) if prev.bpm.to_bits() == segment.bpm.to_bits() => {
+                    prev.end_time = segment.end_time;
+                }
+                _ => segments.push(segment),
+            }
+        }
+
+        let most_common_beat_len = bpm_points
+            .map
+            .into_iter()
+            // * Get the most common one, or 0 as a suitable default
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0.0, |(beat_len, _)| f64::from_bits(beat_len));
+
+        let dominant = 60_000.0 / most_common_beat_len * clock_rate;
+
+        let (min, max) = segments
+            .iter()
+            .filter(|segment| segment.duration() * clock_rate >= Self::MIN_SEGMENT_DURATION)
+            .map(|segment| segment.bpm)
+            .fold(None, |min_max, bpm| match min_max {
+                Some((min, max)) => Some((f64::min(min, bpm), f64::max(max, bpm))),
+                None => Some((bpm, bpm)),
+            })
+            .unwrap_or((dominant, dominant));
+
+        Self {
+            min,
+            max,
+            dominant,
+            segments,
+        }
+    }
+}
+
+fn round_beat_len(beat_len: f64) -> f64 {
+    (1000.0 * beat_len).round() / 1000.0
 }
 
 /// Maps `beat_len` to a cumulative duration
@@ -56,7 +139,6 @@ impl BeatLenDuration {
     }
 
     fn add(&mut self, beat_len: f64, curr_time: f64, next_time: f64) {
-        let beat_len = (1000.0 * beat_len).round() / 1000.0;
         let entry = self.map.entry(beat_len.to_bits()).or_default();
 
         if curr_time <= self.last_time {
@@ -64,3 +146,88 @@ impl BeatLenDuration {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{model::hit_object::HitObjectKind, Beatmap, Difficulty};
+
+    use super::*;
+
+    fn circle(start_time: f64) -> HitObject {
+        HitObject {
+            pos: Default::default(),
+            start_time,
+            kind: HitObjectKind::Circle,
+        }
+    }
+
+    #[test]
+    fn resources() {
+        for (path, expected) in [
+            ("./resources/2785319.osu", 170.0),
+            ("./resources/1028484.osu", 195.0),
+            ("./resources/2118524.osu", 190.0),
+            ("./resources/1638954.osu", 150.0),
+        ] {
+            let map = Beatmap::from_path(path).unwrap();
+            let analysis = map.bpm_analysis(&Difficulty::new());
+
+            assert!((map.bpm() - expected).abs() < 1e-3, "{path}");
+            assert!((analysis.dominant - expected).abs() < 1e-3, "{path}");
+            assert!((analysis.min - expected).abs() < 1e-3, "{path}");
+            assert!((analysis.max - expected).abs() < 1e-3, "{path}");
+        }
+    }
+
+    #[test]
+    fn segments() {
+        let timing_points = [
+            TimingPoint::new(1000.0, 500.0),
+            TimingPoint::new(5000.0, 500.0),
+            TimingPoint::new(9000.0, 250.0),
+            TimingPoint::new(10_000.0, 6.0),
+            TimingPoint::new(10_001.0, 1000.0),
+            TimingPoint::new(13_000.0, 100.0),
+        ];
+
+        let last = circle(12_000.0);
+        let analysis = BpmAnalysis::new(Some(&last), &timing_points, 1.0);
+
+        let expected = [
+            (120.0, 0.0, 9000.0),
+            (240.0, 9000.0, 10_000.0),
+            (10_000.0, 10_000.0, 10_001.0),
+            (60.0, 10_001.0, 12_000.0),
+        ];
+
+        assert_eq!(analysis.segments.len(), expected.len());
+
+        for (segment, (bpm, start_time, end_time)) in analysis.segments.iter().zip(expected) {
+            assert!((segment.bpm - bpm).abs() < 1e-9);
+            assert!((segment.start_time - start_time).abs() < f64::EPSILON);
+            assert!((segment.end_time - end_time).abs() < f64::EPSILON);
+        }
+
+        // The 1ms gimmick segment is ignored
+        assert!((analysis.min - 60.0).abs() < 1e-9);
+        assert!((analysis.max - 240.0).abs() < 1e-9);
+        assert!((analysis.dominant - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scales_with_clock_rate() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let nomod = map.bpm_analysis(&Difficulty::new());
+        let dt = map.bpm_analysis(&Difficulty::new().mods(64));
+
+        assert!((dt.dominant - nomod.dominant * 1.5).abs() < 1e-9);
+        assert!((dt.min - nomod.min * 1.5).abs() < 1e-9);
+        assert!((dt.max - nomod.max * 1.5).abs() < 1e-9);
+
+        for (dt, nomod) in dt.segments.iter().zip(nomod.segments.iter()) {
+            assert!((dt.bpm - nomod.bpm * 1.5).abs() < 1e-9);
+            assert!((dt.end_time - nomod.end_time / 1.5).abs() < 1e-9);
+        }
+    }
+}
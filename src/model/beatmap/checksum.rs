@@ -0,0 +1,149 @@
+use std::{error, fmt, fs, io, path::Path, str::FromStr};
+
+use crate::util::md5;
+
+use super::Beatmap;
+
+/// The MD5 checksum of a `.osu` file.
+///
+/// Its [`Display`] and [`FromStr`] implementations use lowercase
+/// hexadecimal, the same format that scores and replays reference beatmaps
+/// by.
+///
+/// [`Display`]: fmt::Display
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BeatmapChecksum([u8; 16]);
+
+impl BeatmapChecksum {
+    /// Compute the checksum of the content of a `.osu` file.
+    pub fn new(bytes: &[u8]) -> Self {
+        Self(md5::md5(bytes))
+    }
+
+    /// The raw bytes of the checksum.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Whether the checksum matches the given hexadecimal hash, e.g. the
+    /// beatmap hash of a score or replay.
+    ///
+    /// The comparison is case-insensitive.
+    pub fn matches(&self, hash: &str) -> bool {
+        hash.parse::<Self>().is_ok_and(|checksum| checksum == *self)
+    }
+}
+
+impl fmt::Display for BeatmapChecksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for BeatmapChecksum {
+    type Err = ParseChecksumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 32 || !s.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(ParseChecksumError);
+        }
+
+        let mut bytes = [0; 16];
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).map_err(|_| ParseChecksumError)?;
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+/// Error when parsing a [`BeatmapChecksum`] from a string that is not 32
+/// hexadecimal digits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseChecksumError;
+
+impl error::Error for ParseChecksumError {}
+
+impl fmt::Display for ParseChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected MD5 checksum of 32 hexadecimal digits")
+    }
+}
+
+impl Beatmap {
+    /// Parse a [`Beatmap`] alongside the [`BeatmapChecksum`] of the file by
+    /// providing a path to a `.osu` file.
+    pub fn from_path_with_checksum<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(Self, BeatmapChecksum), io::Error> {
+        fs::read(path).and_then(|bytes| Self::from_bytes_with_checksum(&bytes))
+    }
+
+    /// Parse a [`Beatmap`] alongside the [`BeatmapChecksum`] of the content of
+    /// a `.osu` file by providing it as a slice of bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::Beatmap;
+    ///
+    /// let bytes = std::fs::read("./resources/2785319.osu").unwrap();
+    /// let (map, checksum) = Beatmap::from_bytes_with_checksum(&bytes).unwrap();
+    ///
+    /// assert_eq!(checksum.to_string(), "3ca9a5e23c9a4e9332f4f6e2a48865f7");
+    ///
+    /// // Verify against the beatmap hash of a score or replay
+    /// assert!(checksum.matches("3CA9A5E23C9A4E9332F4F6E2A48865F7"));
+    /// ```
+    pub fn from_bytes_with_checksum(bytes: &[u8]) -> Result<(Self, BeatmapChecksum), io::Error> {
+        let map = Self::from_bytes(bytes)?;
+
+        Ok((map, BeatmapChecksum::new(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_round_trip() {
+        let checksum = BeatmapChecksum::new(b"abc");
+        let hex = checksum.to_string();
+
+        assert_eq!(hex, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hex.parse(), Ok(checksum));
+        assert!(checksum.matches(&hex.to_uppercase()));
+    }
+
+    #[test]
+    fn rejects_invalid_hashes() {
+        for hash in [
+            "",
+            "900150983cd24fb0",
+            "z00150983cd24fb0d6963f7d28e17f72",
+            "+900150983cd24fb0d6963f7d28e17f7",
+        ] {
+            assert_eq!(hash.parse::<BeatmapChecksum>(), Err(ParseChecksumError));
+        }
+
+        assert!(!BeatmapChecksum::new(b"abc").matches("é0150983cd24fb0d6963f7d28e17f7"));
+    }
+
+    #[test]
+    fn decodes_with_checksum() {
+        let path = "./resources/2785319.osu";
+        let bytes = fs::read(path).unwrap();
+
+        let (map, checksum) = Beatmap::from_path_with_checksum(path).unwrap();
+
+        assert_eq!(map, Beatmap::from_bytes(&bytes).unwrap());
+        assert_eq!(checksum, BeatmapChecksum::new(&bytes));
+        assert!(checksum.matches("3ca9a5e23c9a4e9332f4f6e2a48865f7"));
+    }
+}
@@ -4,7 +4,7 @@ use rosu_map::{
     section::{
         difficulty::{Difficulty, DifficultyKey, ParseDifficultyError},
         events::{BreakPeriod, EventType, ParseEventTypeError},
-        general::{GameMode, GeneralKey, ParseGameModeError},
+        general::{GameMode, GeneralKey, ParseCountdownTypeError, ParseGameModeError},
         hit_objects::{
             hit_samples::{HitSoundType, ParseHitSoundTypeError},
             HitObjectType, ParseHitObjectTypeError, PathControlPoint, PathType,
@@ -56,6 +56,10 @@ pub struct BeatmapState {
 }
 
 impl BeatmapState {
+    pub(super) fn hit_objects(&self) -> &[HitObject] {
+        &self.hit_objects
+    }
+
     fn add_pending_point<P: Pending>(&mut self, time: f64, point: P, timing_change: bool) {
         if time.not_eq(self.pending_control_points_time) {
             self.flush_pending_points();
@@ -329,7 +333,9 @@ impl From<BeatmapState> for Beatmap {
 
 /// All the ways that parsing a [`Beatmap`] can fail.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ParseBeatmapError {
+    Countdown(ParseCountdownTypeError),
     EffectFlags(ParseEffectFlagsError),
     EventType(ParseEventTypeError),
     HitObjectType(ParseHitObjectTypeError),
@@ -342,12 +348,14 @@ pub enum ParseBeatmapError {
     Number(ParseNumberError),
     TimeSignature,
     TimingControlPointNaN,
+    UnknownFileFormat,
     UnknownHitObjectType,
 }
 
 impl error::Error for ParseBeatmapError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            ParseBeatmapError::Countdown(err) => Some(err),
             ParseBeatmapError::EffectFlags(err) => Some(err),
             ParseBeatmapError::EventType(err) => Some(err),
             ParseBeatmapError::HitObjectType(err) => Some(err),
@@ -360,6 +368,7 @@ impl error::Error for ParseBeatmapError {
             | ParseBeatmapError::InvalidHitObjectLine
             | ParseBeatmapError::TimeSignature
             | ParseBeatmapError::TimingControlPointNaN
+            | ParseBeatmapError::UnknownFileFormat
             | ParseBeatmapError::UnknownHitObjectType => None,
         }
     }
@@ -368,6 +377,7 @@ impl error::Error for ParseBeatmapError {
 impl fmt::Display for ParseBeatmapError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
+            Self::Countdown(_) => "failed to parse countdown type",
             Self::EffectFlags(_) => "failed to parse effect flags",
             Self::EventType(_) => "failed to parse event type",
             Self::HitObjectType(_) => "failed to parse hit object type",
@@ -380,6 +390,7 @@ impl fmt::Display for ParseBeatmapError {
             Self::Number(_) => "failed to parse number",
             Self::TimeSignature => "invalid time signature, must be positive integer",
             Self::TimingControlPointNaN => "beat length cannot be NaN in a timing control point",
+            Self::UnknownFileFormat => "unknown file format",
             Self::UnknownHitObjectType => "unknown hit object type",
         };
 
@@ -387,6 +398,12 @@ impl fmt::Display for ParseBeatmapError {
     }
 }
 
+impl From<ParseCountdownTypeError> for ParseBeatmapError {
+    fn from(err: ParseCountdownTypeError) -> Self {
+        Self::Countdown(err)
+    }
+}
+
 impl From<ParseEffectFlagsError> for ParseBeatmapError {
     fn from(err: ParseEffectFlagsError) -> Self {
         Self::EffectFlags(err)
@@ -0,0 +1,553 @@
+use std::{cmp::Ordering, mem, ops::Range};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{model::hit_object::HitObject, util::float_ext::FloatExt};
+
+use super::Beatmap;
+
+/// Differences between two versions of a [`Beatmap`].
+///
+/// Hit objects and control points are matched by their start time. Hit
+/// objects that could not be matched that way are matched with objects that
+/// start at most 20ms apart. Indices refer to the hit objects of the
+/// respective map version.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BeatmapDiff {
+    /// Old and new mode if it changed.
+    pub mode: Option<(GameMode, GameMode)>,
+    /// Changed settings such as AR or the slider multiplier.
+    pub settings: Vec<SettingChange>,
+    /// Indices of hit objects that are only present in the new map.
+    pub added_objects: Vec<usize>,
+    /// Indices of hit objects that are only present in the old map.
+    pub removed_objects: Vec<usize>,
+    /// Old and new indices of hit objects that differ in position, kind, or
+    /// shape, or whose start time was shifted by at most 20ms.
+    ///
+    /// Among objects with the same start time, unchanged objects and objects
+    /// at the same position are matched first. Shifted objects must be of the
+    /// same kind and are preferably matched by position.
+    pub moved_objects: Vec<(usize, usize)>,
+    /// Old and new indices of otherwise unchanged hit objects whose hit sound
+    /// changed.
+    pub changed_hit_sounds: Vec<(usize, usize)>,
+    /// Times of timing points that were added, removed, or changed.
+    pub changed_timing_points: Vec<f64>,
+    /// Times of difficulty points that were added, removed, or changed.
+    pub changed_difficulty_points: Vec<f64>,
+    /// Times of effect points that were added, removed, or changed.
+    pub changed_effect_points: Vec<f64>,
+    /// Whether any break was added, removed, or changed.
+    pub breaks_changed: bool,
+    /// Whether the changes affect difficulty or performance calculation.
+    ///
+    /// Effect points never affect calculations. Hit sounds only matter for
+    /// osu!taiko and osu!standard maps due to conversion, and breaks only for
+    /// osu!standard maps due to conversion to osu!mania.
+    pub difficulty_relevant: bool,
+}
+
+/// A setting of a [`Beatmap`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BeatmapSetting {
+    /// The format version of the `.osu` file.
+    Version,
+    /// The stack leniency.
+    StackLeniency,
+    /// The approach rate.
+    Ar,
+    /// The circle size.
+    Cs,
+    /// The HP drain rate.
+    Hp,
+    /// The overall difficulty.
+    Od,
+    /// The base slider velocity.
+    SliderMultiplier,
+    /// The amount of slider ticks per beat.
+    SliderTickRate,
+}
+
+/// A changed [`BeatmapSetting`] alongside its old and new value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SettingChange {
+    /// The setting that changed.
+    pub setting: BeatmapSetting,
+    /// The value in the old map.
+    pub old: f64,
+    /// The value in the new map.
+    pub new: f64,
+}
+
+impl BeatmapDiff {
+    /// Whether both map versions are identical.
+    pub fn is_empty(&self) -> bool {
+        self.mode.is_none()
+            && self.settings.is_empty()
+            && self.added_objects.is_empty()
+            && self.removed_objects.is_empty()
+            && self.moved_objects.is_empty()
+            && self.changed_hit_sounds.is_empty()
+            && self.changed_timing_points.is_empty()
+            && self.changed_difficulty_points.is_empty()
+            && self.changed_effect_points.is_empty()
+            && !self.breaks_changed
+    }
+}
+
+impl Beatmap {
+    /// Compare this [`Beatmap`] with a newer version of it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::Beatmap;
+    ///
+    /// let old = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// let mut new = old.clone();
+    /// new.ar = 9.0;
+    ///
+    /// let diff = old.diff(&new);
+    ///
+    /// assert_eq!(diff.settings.len(), 1);
+    /// assert!(diff.difficulty_relevant);
+    /// ```
+    pub fn diff(&self, new: &Self) -> BeatmapDiff {
+        let mut diff = BeatmapDiff {
+            mode: (self.mode != new.mode).then_some((self.mode, new.mode)),
+            settings: changed_settings(self, new),
+            changed_timing_points: changed_points(&self.timing_points, &new.timing_points, |p| {
+                p.time
+            }),
+            changed_difficulty_points: changed_points(
+                &self.difficulty_points,
+                &new.difficulty_points,
+                |p| p.time,
+            ),
+            changed_effect_points: changed_points(&self.effect_points, &new.effect_points, |p| {
+                p.time
+            }),
+            breaks_changed: self.breaks != new.breaks,
+            ..Default::default()
+        };
+
+        diff_hit_objects(self, new, &mut diff);
+
+        let hit_sounds_relevant = matches!(new.mode, GameMode::Osu | GameMode::Taiko);
+        let breaks_relevant = new.mode == GameMode::Osu;
+
+        diff.difficulty_relevant = diff.mode.is_some()
+            || !diff.settings.is_empty()
+            || !diff.added_objects.is_empty()
+            || !diff.removed_objects.is_empty()
+            || !diff.moved_objects.is_empty()
+            || !diff.changed_timing_points.is_empty()
+            || !diff.changed_difficulty_points.is_empty()
+            || (hit_sounds_relevant && !diff.changed_hit_sounds.is_empty())
+            || (breaks_relevant && diff.breaks_changed);
+
+        diff
+    }
+}
+
+fn changed_settings(old: &Beatmap, new: &Beatmap) -> Vec<SettingChange> {
+    let settings = [
+        (
+            BeatmapSetting::Version,
+            f64::from(old.version),
+            f64::from(new.version),
+        ),
+        (
+            BeatmapSetting::StackLeniency,
+            f64::from(old.stack_leniency),
+            f64::from(new.stack_leniency),
+        ),
+        (BeatmapSetting::Ar, f64::from(old.ar), f64::from(new.ar)),
+        (BeatmapSetting::Cs, f64::from(old.cs), f64::from(new.cs)),
+        (BeatmapSetting::Hp, f64::from(old.hp), f64::from(new.hp)),
+        (BeatmapSetting::Od, f64::from(old.od), f64::from(new.od)),
+        (
+            BeatmapSetting::SliderMultiplier,
+            old.slider_multiplier,
+            new.slider_multiplier,
+        ),
+        (
+            BeatmapSetting::SliderTickRate,
+            old.slider_tick_rate,
+            new.slider_tick_rate,
+        ),
+    ];
+
+    settings
+        .into_iter()
+        .filter(|(_, old, new)| old.not_eq(*new))
+        .map(|(setting, old, new)| SettingChange { setting, old, new })
+        .collect()
+}
+
+fn diff_hit_objects(old: &Beatmap, new: &Beatmap, diff: &mut BeatmapDiff) {
+    let mut i = 0;
+    let mut j = 0;
+
+    loop {
+        let time = match (old.hit_objects.get(i), new.hit_objects.get(j)) {
+            (Some(a), Some(b)) => a.start_time.min(b.start_time),
+            (Some(h), None) | (None, Some(h)) => h.start_time,
+            (None, None) => break,
+        };
+
+        let starts_at_time = |h: &HitObject| h.start_time.total_cmp(&time).is_eq();
+
+        let old_end = i + old.hit_objects[i..]
+            .iter()
+            .take_while(|h| starts_at_time(h))
+            .count();
+
+        let new_end = j + new.hit_objects[j..]
+            .iter()
+            .take_while(|h| starts_at_time(h))
+            .count();
+
+        diff_simultaneous_objects(old, new, i..old_end, j..new_end, diff);
+
+        i = old_end;
+        j = new_end;
+    }
+
+    match_shifted_objects(old, new, diff);
+
+    diff.moved_objects.sort_unstable();
+    diff.changed_hit_sounds.sort_unstable();
+}
+
+/// Match hit objects that start at the same time, e.g. osu!mania chords or
+/// stacked objects.
+///
+/// Identical objects are matched first, then objects at the same position or
+/// column, and the remaining ones in order.
+fn diff_simultaneous_objects(
+    old: &Beatmap,
+    new: &Beatmap,
+    old_range: Range<usize>,
+    new_range: Range<usize>,
+    diff: &mut BeatmapDiff,
+) {
+    let mut unmatched_new: Vec<_> = new_range.collect();
+    let mut unmatched_old = Vec::new();
+
+    for i in old_range {
+        let a = &old.hit_objects[i];
+
+        let Some(k) = unmatched_new.iter().position(|&j| *a == new.hit_objects[j]) else {
+            unmatched_old.push(i);
+
+            continue;
+        };
+
+        let j = unmatched_new.remove(k);
+
+        if old.hit_sounds.get(i) != new.hit_sounds.get(j) {
+            diff.changed_hit_sounds.push((i, j));
+        }
+    }
+
+    unmatched_old.retain(|&i| {
+        let pos = old.hit_objects[i].pos;
+
+        let Some(k) = unmatched_new
+            .iter()
+            .position(|&j| new.hit_objects[j].pos == pos)
+        else {
+            return true;
+        };
+
+        diff.moved_objects.push((i, unmatched_new.remove(k)));
+
+        false
+    });
+
+    let n_paired = unmatched_old.len().min(unmatched_new.len());
+
+    diff.moved_objects.extend(
+        unmatched_old
+            .iter()
+            .copied()
+            .zip(unmatched_new.iter().copied()),
+    );
+    diff.removed_objects.extend(&unmatched_old[n_paired..]);
+    diff.added_objects.extend(&unmatched_new[n_paired..]);
+}
+
+/// Maximum difference in ms between the start times of a removed and an added
+/// hit object to consider them the same but shifted object.
+const SHIFT_TOLERANCE: f64 = 20.0;
+
+/// Match removed and added hit objects of the same kind that start close to
+/// each other, preferably at the same position.
+fn match_shifted_objects(old: &Beatmap, new: &Beatmap, diff: &mut BeatmapDiff) {
+    let removed = mem::take(&mut diff.removed_objects);
+
+    let mut added: Vec<_> = mem::take(&mut diff.added_objects)
+        .into_iter()
+        .map(Some)
+        .collect();
+
+    // Index of the first added object that may still be within tolerance
+    let mut first = 0;
+
+    for i in removed {
+        let a = &old.hit_objects[i];
+
+        while let Some(&entry) = added.get(first) {
+            match entry {
+                Some(j) if new.hit_objects[j].start_time >= a.start_time - SHIFT_TOLERANCE => break,
+                _ => first += 1,
+            }
+        }
+
+        let candidates = added[first..]
+            .iter()
+            .enumerate()
+            .filter_map(|(k, j)| j.map(|j| (first + k, &new.hit_objects[j])))
+            .take_while(|(_, b)| b.start_time <= a.start_time + SHIFT_TOLERANCE)
+            .filter(|(_, b)| mem::discriminant(&a.kind) == mem::discriminant(&b.kind));
+
+        let mut matched = None;
+
+        for (k, b) in candidates {
+            if b.pos == a.pos {
+                matched = Some(k);
+
+                break;
+            }
+
+            matched = matched.or(Some(k));
+        }
+
+        match matched.and_then(|k| added[k].take()) {
+            Some(j) => diff.moved_objects.push((i, j)),
+            None => diff.removed_objects.push(i),
+        }
+    }
+
+    diff.added_objects.extend(added.into_iter().flatten());
+}
+
+/// Times at which the control points of both lists differ.
+fn changed_points<T: PartialEq>(old: &[T], new: &[T], time: impl Fn(&T) -> f64) -> Vec<f64> {
+    let mut changed = Vec::new();
+    let mut i = 0;
+    let mut j = 0;
+
+    loop {
+        let (a, b) = (old.get(i), new.get(j));
+
+        let changed_time = match (a, b) {
+            (Some(a), Some(b)) => match time(a).total_cmp(&time(b)) {
+                Ordering::Less => {
+                    i += 1;
+
+                    time(a)
+                }
+                Ordering::Greater => {
+                    j += 1;
+
+                    time(b)
+                }
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+
+                    if a == b {
+                        continue;
+                    }
+
+                    time(a)
+                }
+            },
+            (Some(a), None) => {
+                i += 1;
+
+                time(a)
+            }
+            (None, Some(b)) => {
+                j += 1;
+
+                time(b)
+            }
+            (None, None) => break,
+        };
+
+        changed.push(changed_time);
+    }
+
+    changed.dedup_by(|a, b| FloatExt::eq(*a, *b));
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_map::section::{events::BreakPeriod, hit_objects::hit_samples::HitSoundType};
+
+    use crate::model::{
+        control_point::{DifficultyPoint, EffectPoint},
+        hit_object::{HitObject, HitObjectKind, Pos},
+    };
+
+    use super::*;
+
+    #[test]
+    fn identical_maps() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let diff = map.diff(&map.clone());
+
+        assert!(diff.is_empty());
+        assert!(!diff.difficulty_relevant);
+    }
+
+    #[test]
+    fn hit_objects() {
+        let old = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut new = old.clone();
+
+        new.hit_objects[10].pos += Pos::new(5.0, 0.0);
+        new.hit_objects.remove(20);
+        new.hit_sounds.remove(20);
+
+        let added = HitObject {
+            pos: Pos::default(),
+            start_time: new.hit_objects[30].start_time + 1.0,
+            kind: HitObjectKind::Circle,
+        };
+
+        new.hit_objects.insert(31, added);
+        new.hit_sounds.insert(31, HitSoundType::default());
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.moved_objects, [(10, 10)]);
+        assert_eq!(diff.removed_objects, [20]);
+        assert_eq!(diff.added_objects, [31]);
+        assert!(diff.changed_hit_sounds.is_empty());
+        assert!(diff.difficulty_relevant);
+    }
+
+    #[test]
+    fn shifted_objects() {
+        let old = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut new = old.clone();
+
+        for h in new.hit_objects.iter_mut() {
+            h.start_time += 5.0;
+        }
+
+        let diff = old.diff(&new);
+        let n_objects = old.hit_objects.len();
+
+        assert_eq!(diff.moved_objects.len(), n_objects);
+        assert!(diff.moved_objects.iter().all(|(i, j)| i == j));
+        assert!(diff.removed_objects.is_empty());
+        assert!(diff.added_objects.is_empty());
+
+        // Shifting too far counts as removed and added
+        for h in new.hit_objects.iter_mut() {
+            h.start_time += 1_000_000.0;
+        }
+
+        let diff = old.diff(&new);
+
+        assert!(diff.moved_objects.is_empty());
+        assert_eq!(diff.removed_objects.len(), n_objects);
+        assert_eq!(diff.added_objects.len(), n_objects);
+    }
+
+    #[test]
+    fn simultaneous_objects() {
+        let old = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let chord = old
+            .hit_objects
+            .windows(2)
+            .position(|pair| pair[0].start_time.total_cmp(&pair[1].start_time).is_eq())
+            .unwrap();
+
+        let mut new = old.clone();
+        new.hit_objects.remove(chord);
+        new.hit_sounds.remove(chord);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.removed_objects, [chord]);
+        assert!(diff.added_objects.is_empty());
+        assert!(diff.moved_objects.is_empty());
+
+        // Moving a note within a chord only affects that note
+        let mut new = old.clone();
+        new.hit_objects[chord + 1].pos += Pos::new(100.0, 0.0);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.moved_objects, [(chord + 1, chord + 1)]);
+        assert!(diff.removed_objects.is_empty());
+        assert!(diff.added_objects.is_empty());
+    }
+
+    #[test]
+    fn settings_and_points() {
+        let old = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut new = old.clone();
+
+        new.od += 1.0;
+        new.slider_multiplier *= 2.0;
+        new.difficulty_points
+            .push(DifficultyPoint::new(1_000_000.0, -50.0, 1.0));
+
+        let diff = old.diff(&new);
+
+        let settings: Vec<_> = diff.settings.iter().map(|change| change.setting).collect();
+        assert_eq!(
+            settings,
+            [BeatmapSetting::Od, BeatmapSetting::SliderMultiplier]
+        );
+        assert_eq!(diff.changed_difficulty_points, [1_000_000.0]);
+        assert!(diff.changed_timing_points.is_empty());
+        assert!(diff.difficulty_relevant);
+    }
+
+    #[test]
+    fn irrelevant_changes() {
+        let old = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let mut new = old.clone();
+
+        new.effect_points.push(EffectPoint::new(1_000_000.0, true));
+        new.breaks.push(BreakPeriod {
+            start_time: 1_000_000.0,
+            end_time: 1_010_000.0,
+        });
+        new.hit_sounds[0] = HitSoundType::from(u8::from(old.hit_sounds[0]) ^ HitSoundType::CLAP);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.changed_effect_points, [1_000_000.0]);
+        assert_eq!(diff.changed_hit_sounds, [(0, 0)]);
+        assert!(diff.breaks_changed);
+        assert!(!diff.is_empty());
+        assert!(!diff.difficulty_relevant);
+    }
+
+    #[test]
+    fn taiko_hit_sounds_are_relevant() {
+        let old = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let mut new = old.clone();
+
+        new.hit_sounds[0] = HitSoundType::from(u8::from(old.hit_sounds[0]) ^ HitSoundType::CLAP);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.changed_hit_sounds, [(0, 0)]);
+        assert!(diff.difficulty_relevant);
+    }
+}
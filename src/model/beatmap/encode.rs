@@ -0,0 +1,461 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use rosu_map::{
+    section::{
+        general::GameMode,
+        hit_objects::{hit_samples::HitSoundType, PathControlPoint, PathType, SplineType},
+    },
+    util::Pos,
+};
+
+use crate::{
+    model::{
+        control_point::{DifficultyPoint, TimingPoint},
+        hit_object::{HitObject, HitObjectKind, HoldNote, Slider, Spinner},
+    },
+    util::sort,
+};
+
+use super::Beatmap;
+
+const CIRCLE: u8 = 1 << 0;
+const SLIDER: u8 = 1 << 1;
+const SPINNER: u8 = 1 << 3;
+const HOLD: u8 = 1 << 7;
+
+const KIAI: u8 = 1 << 0;
+
+impl Beatmap {
+    /// Encode the [`Beatmap`] into the content of a `.osu` file and store it
+    /// at the given path.
+    pub fn encode_to_path<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        self.encode(&mut writer)?;
+
+        writer.flush()
+    }
+
+    /// Encode the [`Beatmap`] into the content of a `.osu` file and store it
+    /// into a [`String`].
+    pub fn encode_to_string(&self) -> io::Result<String> {
+        let mut writer = Vec::with_capacity(4096);
+        self.encode(&mut writer)?;
+
+        String::from_utf8(writer).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Encode the [`Beatmap`] into the content of a `.osu` file.
+    ///
+    /// Only the data that is stored in a [`Beatmap`] is written so decoding
+    /// the output results in the same [`Beatmap`] again. Converted maps are
+    /// written as maps of their converted mode, i.e. decoding them does not
+    /// set [`Beatmap::is_convert`].
+    ///
+    /// In case of writing directly to a file, it is recommended to pass the
+    /// file wrapped in a [`BufWriter`] or to use [`Beatmap::encode_to_path`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::Beatmap;
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// let mut bytes = Vec::new();
+    /// map.encode(&mut bytes).unwrap();
+    ///
+    /// assert_eq!(Beatmap::from_bytes(&bytes).unwrap(), map);
+    /// ```
+    pub fn encode<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "osu file format v{}", self.version)?;
+
+        self.encode_general(&mut writer)?;
+        self.encode_metadata(&mut writer)?;
+        self.encode_difficulty(&mut writer)?;
+        self.encode_events(&mut writer)?;
+        self.encode_timing_points(&mut writer)?;
+        self.encode_hit_objects(&mut writer)
+    }
+
+    fn encode_general<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mode = match self.mode {
+            GameMode::Osu => 0,
+            GameMode::Taiko => 1,
+            GameMode::Catch => 2,
+            GameMode::Mania => 3,
+        };
+
+        writeln!(writer, "\n[General]")?;
+        writeln!(writer, "StackLeniency: {}", self.stack_leniency)?;
+        writeln!(writer, "Mode: {mode}")
+    }
+
+    fn encode_metadata<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "\n[Metadata]")?;
+        writeln!(writer, "Creator:{}", self.creator)?;
+        writeln!(writer, "BeatmapID:{}", self.beatmap_id)
+    }
+
+    fn encode_difficulty<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "\n[Difficulty]")?;
+        writeln!(writer, "HPDrainRate:{}", self.hp)?;
+        writeln!(writer, "CircleSize:{}", self.cs)?;
+        writeln!(writer, "OverallDifficulty:{}", self.od)?;
+        writeln!(writer, "ApproachRate:{}", self.ar)?;
+        writeln!(writer, "SliderMultiplier:{}", self.slider_multiplier)?;
+        writeln!(writer, "SliderTickRate:{}", self.slider_tick_rate)
+    }
+
+    fn encode_events<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "\n[Events]")?;
+
+        for period in self.breaks.iter() {
+            writeln!(writer, "2,{},{}", period.start_time, period.end_time)?;
+        }
+
+        Ok(())
+    }
+
+    fn encode_timing_points<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "\n[TimingPoints]")?;
+
+        let mut timing_points = self.timing_points.iter().peekable();
+        let mut difficulty_points = self.difficulty_points.iter().peekable();
+        let mut effect_points = self.effect_points.iter().peekable();
+
+        loop {
+            let time = [
+                timing_points.peek().map(|point| point.time),
+                difficulty_points.peek().map(|point| point.time),
+                effect_points.peek().map(|point| point.time),
+            ]
+            .into_iter()
+            .flatten()
+            .min_by(f64::total_cmp);
+
+            let Some(time) = time else { break };
+
+            let timing = timing_points.next_if(|point| point.time.total_cmp(&time).is_eq());
+            let difficulty = difficulty_points.next_if(|point| point.time.total_cmp(&time).is_eq());
+            let effect = effect_points.next_if(|point| point.time.total_cmp(&time).is_eq());
+
+            let effects = if self.effect_point_at(time).is_some_and(|point| point.kiai) {
+                KIAI
+            } else {
+                0
+            };
+
+            if let Some(TimingPoint { time, beat_len }) = timing {
+                writeln!(writer, "{time},{beat_len},4,0,0,100,1,{effects}")?;
+            }
+
+            // Timing points reset the slider velocity so the current one needs
+            // to be written again unless it is the default anyway.
+            let active = self.difficulty_point_at(time);
+
+            let difficulty = difficulty.or_else(|| {
+                timing
+                    .and(active)
+                    .filter(|point| !point.is_redundant(&DifficultyPoint::default()))
+            });
+
+            // Effect-only changes require an inherited line as well
+            let difficulty = difficulty.or_else(|| {
+                effect
+                    .filter(|_| timing.is_none())
+                    .map(|_| active.unwrap_or(&DEFAULT_DIFFICULTY_POINT))
+            });
+
+            if let Some(point) = difficulty {
+                let beat_len = inherited_beat_len(point);
+                writeln!(writer, "{time},{beat_len},4,0,0,100,0,{effects}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_hit_objects<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "\n[HitObjects]")?;
+
+        let hit_objects = if self.mode == GameMode::Mania {
+            mania_file_order(&self.hit_objects)
+        } else {
+            self.hit_objects.iter().collect()
+        };
+
+        // Converted osu!mania maps do not store hit sounds
+        let hit_sounds = self
+            .hit_sounds
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(HitSoundType::default()));
+
+        for (h, sound) in hit_objects.into_iter().zip(hit_sounds) {
+            let HitObject {
+                pos,
+                start_time,
+                ref kind,
+            } = *h;
+
+            let sound = u8::from(sound);
+
+            let kind_flag = match kind {
+                HitObjectKind::Circle => CIRCLE,
+                HitObjectKind::Slider(_) => SLIDER,
+                HitObjectKind::Spinner(_) => SPINNER,
+                HitObjectKind::Hold(_) => HOLD,
+            };
+
+            write!(
+                writer,
+                "{x},{y},{start_time},{kind_flag},{sound},",
+                x = pos.x,
+                y = pos.y
+            )?;
+
+            match kind {
+                HitObjectKind::Circle => {}
+                HitObjectKind::Slider(slider) => encode_slider(writer, slider, pos)?,
+                HitObjectKind::Spinner(Spinner { duration }) => {
+                    write!(writer, "{},", start_time + duration)?;
+                }
+                HitObjectKind::Hold(HoldNote { duration }) => {
+                    write!(writer, "{}:", start_time + duration)?;
+                }
+            }
+
+            writeln!(writer, "0:0:0:0:")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The order in which osu!mania hit objects must be written so that osu!'s
+/// legacy sorting during decoding restores their current order.
+///
+/// Hit sounds are not sorted that way so they keep their order.
+fn mania_file_order(hit_objects: &[HitObject]) -> Vec<&HitObject> {
+    // Sorting only depends on start times so the index can be tracked
+    // through the position.
+    let mut sorted: Vec<_> = hit_objects
+        .iter()
+        .enumerate()
+        .map(|(i, h)| HitObject {
+            pos: Pos::new(i as f32, 0.0),
+            start_time: h.start_time,
+            kind: HitObjectKind::Circle,
+        })
+        .collect();
+
+    sort::osu_legacy(&mut sorted);
+
+    let mut order: Vec<_> = hit_objects.iter().collect();
+
+    for (h, sorted) in hit_objects.iter().zip(sorted.iter()) {
+        order[sorted.pos.x as usize] = h;
+    }
+
+    order
+}
+
+const DEFAULT_DIFFICULTY_POINT: DifficultyPoint = DifficultyPoint {
+    time: 0.0,
+    slider_velocity: DifficultyPoint::DEFAULT_SLIDER_VELOCITY,
+    bpm_multiplier: DifficultyPoint::DEFAULT_BPM_MULTIPLIER,
+    generate_ticks: DifficultyPoint::DEFAULT_GENERATE_TICKS,
+};
+
+/// The beat length of an inherited timing point that decodes into the given
+/// [`DifficultyPoint`].
+fn inherited_beat_len(point: &DifficultyPoint) -> f64 {
+    if !point.generate_ticks {
+        return f64::NAN;
+    }
+
+    let decodes_into = |beat_len: f64| {
+        let decoded = DifficultyPoint::new(point.time, beat_len, 100.0 / -beat_len);
+
+        decoded.slider_velocity.to_bits() == point.slider_velocity.to_bits()
+            && decoded.bpm_multiplier.to_bits() == point.bpm_multiplier.to_bits()
+    };
+
+    let candidates = [
+        -100.0 / point.slider_velocity,
+        -100.0 * point.bpm_multiplier,
+    ];
+
+    // Division and clamping may be off by a few ulps so the neighbours of
+    // each candidate are checked as well.
+    candidates
+        .into_iter()
+        .flat_map(|beat_len| {
+            (-4..=4).map(move |ulps| f64::from_bits(beat_len.to_bits().wrapping_add_signed(ulps)))
+        })
+        .find(|&beat_len| decodes_into(beat_len))
+        .unwrap_or(candidates[0])
+}
+
+fn encode_slider<W: Write>(writer: &mut W, slider: &Slider, pos: Pos) -> io::Result<()> {
+    encode_path(writer, &slider.control_points, pos)?;
+
+    write!(
+        writer,
+        "{span_count},{dist},",
+        span_count = slider.repeats + 1,
+        dist = slider.expected_dist.unwrap_or(0.0),
+    )?;
+
+    for (i, sound) in slider.node_sounds.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "|" };
+        write!(writer, "{separator}{}", u8::from(*sound))?;
+    }
+
+    writer.write_all(b",")?;
+
+    for i in 0..slider.node_sounds.len() {
+        let separator = if i == 0 { "" } else { "|" };
+        write!(writer, "{separator}0:0")?;
+    }
+
+    writer.write_all(b",")
+}
+
+fn encode_path<W: Write>(
+    writer: &mut W,
+    control_points: &[PathControlPoint],
+    pos: Pos,
+) -> io::Result<()> {
+    let separator = |i: usize| {
+        if i == control_points.len() - 1 {
+            ','
+        } else {
+            '|'
+        }
+    };
+
+    let mut last_type = None;
+
+    for (i, point) in control_points.iter().enumerate() {
+        if let Some(path_type) = point.path_type {
+            let mut needs_explicit_segment =
+                point.path_type != last_type || path_type == PathType::PERFECT_CURVE;
+
+            // Duplicated positions within a segment would otherwise be
+            // decoded as segment separator.
+            if i > 1 {
+                let p1 = pos + control_points[i - 1].pos;
+                let p2 = pos + control_points[i - 2].pos;
+
+                if p1.x as i32 == p2.x as i32 && p1.y as i32 == p2.y as i32 {
+                    needs_explicit_segment = true;
+                }
+            }
+
+            if needs_explicit_segment {
+                match path_type.kind {
+                    SplineType::BSpline => match path_type.degree {
+                        Some(degree) => write!(writer, "B{degree}")?,
+                        None => writer.write_all(b"B")?,
+                    },
+                    SplineType::Catmull => writer.write_all(b"C")?,
+                    SplineType::PerfectCurve => writer.write_all(b"P")?,
+                    SplineType::Linear => writer.write_all(b"L")?,
+                }
+
+                write!(writer, "{}", separator(i))?;
+
+                last_type = point.path_type;
+            } else {
+                // Segments of the same type are separated by a duplicated
+                // control point.
+                write!(
+                    writer,
+                    "{x}:{y}|",
+                    x = pos.x + point.pos.x,
+                    y = pos.y + point.pos.y
+                )?;
+            }
+        }
+
+        if i != 0 {
+            write!(
+                writer,
+                "{x}:{y}{separator}",
+                x = pos.x + point.pos.x,
+                y = pos.y + point.pos.y,
+                separator = separator(i),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{model::control_point::EffectPoint, GameMods};
+
+    use super::*;
+
+    fn assert_round_trip(map: &Beatmap) {
+        let encoded = map.encode_to_string().unwrap();
+        let decoded: Beatmap = encoded.parse().unwrap();
+
+        let mut expected = Beatmap {
+            is_convert: false,
+            ..map.clone()
+        };
+
+        expected
+            .hit_sounds
+            .resize(expected.hit_objects.len(), HitSoundType::default());
+
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let map = Beatmap::from_path(path).unwrap();
+            assert_round_trip(&map);
+        }
+    }
+
+    #[test]
+    fn round_trip_converts() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for mode in [GameMode::Taiko, GameMode::Catch, GameMode::Mania] {
+            let converted = map.convert_ref(mode, &GameMods::default()).unwrap();
+
+            assert!(converted.is_convert);
+            assert_round_trip(&converted);
+        }
+    }
+
+    #[test]
+    fn control_points() {
+        let mut map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        map.timing_points.push(TimingPoint::new(200_000.0, 250.0));
+        map.effect_points.push(EffectPoint::new(200_500.0, true));
+        map.difficulty_points
+            .push(DifficultyPoint::new(201_000.0, -75.0, 100.0 / 75.0));
+        map.difficulty_points
+            .push(DifficultyPoint::new(202_000.0, f64::NAN, 1.0));
+
+        assert_round_trip(&map);
+    }
+}
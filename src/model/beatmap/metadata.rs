@@ -0,0 +1,350 @@
+use std::{io, path::Path};
+
+use rosu_map::{
+    section::{
+        general::{CountdownType, GeneralKey},
+        metadata::MetadataKey,
+    },
+    util::{KeyValue, ParseNumber, StrExt},
+    DecodeBeatmap, DecodeState,
+};
+
+use super::{Beatmap, BeatmapState, ParseBeatmapError};
+
+/// Descriptive info of a `.osu` file's `[General]` and `[Metadata]` sections
+/// that is not required for calculations and thus not stored in a
+/// [`Beatmap`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeatmapMetadata {
+    // General
+    /// Milliseconds of silence before the audio starts playing.
+    pub audio_lead_in: f64,
+    /// Time in ms of the audio preview in song select, `-1` if unset.
+    pub preview_time: i32,
+    /// Speed of the countdown before the first hit object.
+    pub countdown: CountdownType,
+    /// Whether osu!mania maps use the N+1 key layout.
+    pub special_style: bool,
+    /// Whether breaks are shown with letterboxing.
+    pub letterbox_in_breaks: bool,
+
+    // Metadata
+    /// The romanised song title.
+    pub title: String,
+    /// The song title in its original language.
+    pub title_unicode: String,
+    /// The romanised artist name.
+    pub artist: String,
+    /// The artist name in its original language.
+    pub artist_unicode: String,
+    /// The difficulty name.
+    pub version: String,
+    /// The original media of the song, e.g. a game or anime.
+    pub source: String,
+    /// Space-separated search terms.
+    pub tags: String,
+    /// The id of the beatmapset, `0` if unset.
+    pub beatmap_set_id: i32,
+}
+
+impl BeatmapMetadata {
+    /// Parse [`BeatmapMetadata`] by providing a path to a `.osu` file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::model::beatmap::BeatmapMetadata;
+    ///
+    /// let metadata = BeatmapMetadata::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// println!("{} - {} [{}]", metadata.artist, metadata.title, metadata.version);
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        rosu_map::from_path(path)
+    }
+
+    /// Parse [`BeatmapMetadata`] by providing the content of a `.osu` file as
+    /// a slice of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
+        rosu_map::from_bytes(bytes)
+    }
+}
+
+impl Default for BeatmapMetadata {
+    fn default() -> Self {
+        Self {
+            audio_lead_in: 0.0,
+            preview_time: -1,
+            countdown: CountdownType::Normal,
+            special_style: false,
+            letterbox_in_breaks: false,
+            title: String::new(),
+            title_unicode: String::new(),
+            artist: String::new(),
+            artist_unicode: String::new(),
+            version: String::new(),
+            source: String::new(),
+            tags: String::new(),
+            beatmap_set_id: 0,
+        }
+    }
+}
+
+impl Beatmap {
+    /// Parse a [`Beatmap`] alongside its [`BeatmapMetadata`] by providing a
+    /// path to a `.osu` file.
+    ///
+    /// The file is only read once.
+    pub fn from_path_with_metadata<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(Self, BeatmapMetadata), io::Error> {
+        rosu_map::from_path(path).map(|WithMetadata { map, metadata }| (map, metadata))
+    }
+
+    /// Parse a [`Beatmap`] alongside its [`BeatmapMetadata`] by providing the
+    /// content of a `.osu` file as a slice of bytes.
+    ///
+    /// The content is only read once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::Beatmap;
+    ///
+    /// let bytes = std::fs::read("./resources/2785319.osu").unwrap();
+    /// let (map, metadata) = Beatmap::from_bytes_with_metadata(&bytes).unwrap();
+    ///
+    /// println!("{} by {}", metadata.title, map.creator);
+    /// ```
+    pub fn from_bytes_with_metadata(bytes: &[u8]) -> Result<(Self, BeatmapMetadata), io::Error> {
+        rosu_map::from_bytes(bytes).map(|WithMetadata { map, metadata }| (map, metadata))
+    }
+}
+
+impl DecodeState for BeatmapMetadata {
+    fn create(_: i32) -> Self {
+        Self::default()
+    }
+}
+
+impl DecodeBeatmap for BeatmapMetadata {
+    type Error = ParseBeatmapError;
+    type State = Self;
+
+    fn parse_general(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        let Ok(KeyValue { key, value }) = KeyValue::parse(line.trim_comment()) else {
+            return Ok(());
+        };
+
+        match key {
+            GeneralKey::AudioLeadIn => state.audio_lead_in = f64::from(i32::parse(value)?),
+            GeneralKey::PreviewTime => state.preview_time = i32::parse(value)?,
+            GeneralKey::Countdown => state.countdown = value.parse()?,
+            GeneralKey::SpecialStyle => state.special_style = i32::parse(value)? == 1,
+            GeneralKey::LetterboxInBreaks => state.letterbox_in_breaks = i32::parse(value)? == 1,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn parse_editor(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_metadata(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        // Comments are not trimmed since titles or tags may contain `//`
+        let Ok(KeyValue { key, value }) = KeyValue::parse(line) else {
+            return Ok(());
+        };
+
+        match key {
+            MetadataKey::Title => value.clone_into(&mut state.title),
+            MetadataKey::TitleUnicode => value.clone_into(&mut state.title_unicode),
+            MetadataKey::Artist => value.clone_into(&mut state.artist),
+            MetadataKey::ArtistUnicode => value.clone_into(&mut state.artist_unicode),
+            MetadataKey::Version => value.clone_into(&mut state.version),
+            MetadataKey::Source => value.clone_into(&mut state.source),
+            MetadataKey::Tags => value.clone_into(&mut state.tags),
+            MetadataKey::BeatmapSetID => state.beatmap_set_id = value.parse_num()?,
+            MetadataKey::Creator | MetadataKey::BeatmapID => {}
+        }
+
+        Ok(())
+    }
+
+    fn parse_difficulty(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_events(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_timing_points(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_colors(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_hit_objects(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_variables(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_catch_the_beat(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_mania(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Decodes a [`Beatmap`] and its [`BeatmapMetadata`] in a single pass.
+struct WithMetadata {
+    map: Beatmap,
+    metadata: BeatmapMetadata,
+}
+
+struct WithMetadataState {
+    map: BeatmapState,
+    metadata: BeatmapMetadata,
+}
+
+impl DecodeState for WithMetadataState {
+    fn create(version: i32) -> Self {
+        Self {
+            map: BeatmapState::create(version),
+            metadata: BeatmapMetadata::create(version),
+        }
+    }
+}
+
+impl From<WithMetadataState> for WithMetadata {
+    fn from(state: WithMetadataState) -> Self {
+        Self {
+            map: state.map.into(),
+            metadata: state.metadata,
+        }
+    }
+}
+
+impl DecodeBeatmap for WithMetadata {
+    type Error = ParseBeatmapError;
+    type State = WithMetadataState;
+
+    fn parse_general(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        let map_res = Beatmap::parse_general(&mut state.map, line);
+        let metadata_res = BeatmapMetadata::parse_general(&mut state.metadata, line);
+
+        map_res.and(metadata_res)
+    }
+
+    fn parse_editor(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_editor(&mut state.map, line)
+    }
+
+    fn parse_metadata(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        let map_res = Beatmap::parse_metadata(&mut state.map, line);
+        let metadata_res = BeatmapMetadata::parse_metadata(&mut state.metadata, line);
+
+        map_res.and(metadata_res)
+    }
+
+    fn parse_difficulty(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_difficulty(&mut state.map, line)
+    }
+
+    fn parse_events(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_events(&mut state.map, line)
+    }
+
+    fn parse_timing_points(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_timing_points(&mut state.map, line)
+    }
+
+    fn parse_colors(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_colors(&mut state.map, line)
+    }
+
+    fn parse_hit_objects(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_hit_objects(&mut state.map, line)
+    }
+
+    fn parse_variables(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_variables(&mut state.map, line)
+    }
+
+    fn parse_catch_the_beat(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_catch_the_beat(&mut state.map, line)
+    }
+
+    fn parse_mania(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        Beatmap::parse_mania(&mut state.map, line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_rosu_map() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let (map, metadata) = Beatmap::from_path_with_metadata(path).unwrap();
+
+            assert_eq!(map, Beatmap::from_path(path).unwrap());
+            assert_eq!(metadata, BeatmapMetadata::from_path(path).unwrap());
+
+            let expected = rosu_map::Beatmap::from_path(path).unwrap();
+
+            assert_eq!(metadata.title, expected.title);
+            assert_eq!(metadata.title_unicode, expected.title_unicode);
+            assert_eq!(metadata.artist, expected.artist);
+            assert_eq!(metadata.artist_unicode, expected.artist_unicode);
+            assert_eq!(metadata.version, expected.version);
+            assert_eq!(metadata.source, expected.source);
+            assert_eq!(metadata.tags, expected.tags);
+            assert_eq!(metadata.beatmap_set_id, expected.beatmap_set_id);
+            assert!((metadata.audio_lead_in - expected.audio_lead_in).abs() < f64::EPSILON);
+            assert_eq!(metadata.preview_time, expected.preview_time);
+            assert_eq!(metadata.countdown, expected.countdown);
+            assert_eq!(metadata.special_style, expected.special_style);
+            assert_eq!(metadata.letterbox_in_breaks, expected.letterbox_in_breaks);
+        }
+    }
+
+    #[test]
+    fn keeps_comments_in_metadata() {
+        let bytes = b"osu file format v14
+
+[General]
+AudioLeadIn: 1500 // comment
+Countdown: 2
+SpecialStyle: 1
+
+[Metadata]
+Title:A // B
+BeatmapSetID:42";
+
+        let metadata = BeatmapMetadata::from_bytes(bytes).unwrap();
+
+        assert!((metadata.audio_lead_in - 1500.0).abs() < f64::EPSILON);
+        assert_eq!(metadata.countdown, CountdownType::HalfSpeed);
+        assert!(metadata.special_style);
+        assert!(!metadata.letterbox_in_breaks);
+        assert_eq!(metadata.title, "A // B");
+        assert_eq!(metadata.beatmap_set_id, 42);
+    }
+}
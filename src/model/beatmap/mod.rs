@@ -14,7 +14,13 @@ use crate::{
 
 pub use self::{
     attributes::{BeatmapAttributes, BeatmapAttributesBuilder, HitWindows},
+    bpm::{BpmAnalysis, BpmSegment},
+    checksum::{BeatmapChecksum, ParseChecksumError},
     decode::{BeatmapState, ParseBeatmapError},
+    diff::{BeatmapDiff, BeatmapSetting, SettingChange},
+    metadata::BeatmapMetadata,
+    statistics::BeatmapStatistics,
+    strict::{DecodeError, DecodeOptions, LineError},
 };
 
 use super::{
@@ -28,7 +34,14 @@ use super::{
 
 mod attributes;
 mod bpm;
+mod checksum;
 mod decode;
+mod diff;
+mod encode;
+mod metadata;
+mod slice;
+mod statistics;
+mod strict;
 
 /// All beatmap data that is relevant for difficulty and performance
 /// calculation.
@@ -88,6 +101,31 @@ impl Beatmap {
         bpm::bpm(self.hit_objects.last(), &self.timing_points)
     }
 
+    /// Analyze the BPM changes of the map.
+    ///
+    /// Only the clock rate of the [`Difficulty`] is considered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    /// let analysis = map.bpm_analysis(&Difficulty::new().mods(64));
+    ///
+    /// println!(
+    ///     "{:.0}-{:.0} ({:.0}) BPM",
+    ///     analysis.min, analysis.max, analysis.dominant,
+    /// );
+    /// ```
+    pub fn bpm_analysis(&self, difficulty: &Difficulty) -> BpmAnalysis {
+        BpmAnalysis::new(
+            self.hit_objects.last(),
+            &self.timing_points,
+            difficulty.get_clock_rate(),
+        )
+    }
+
     /// Create a performance calculator for this [`Beatmap`].
     pub fn performance(&self) -> Performance<'_> {
         Performance::new(self)
@@ -0,0 +1,248 @@
+use std::ops::{Bound, Range, RangeBounds};
+
+use rosu_map::section::events::BreakPeriod;
+
+use crate::model::hit_object::HitObject;
+
+use super::Beatmap;
+
+impl Beatmap {
+    /// Create a new [`Beatmap`] that only contains the hit objects starting
+    /// within `start_time..end_time` in ms.
+    ///
+    /// Control points that are active within the range are kept and breaks are
+    /// clipped to it. Times are not shifted, i.e. they remain in map time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// // Difficulty of a practice section
+    /// let section = map.slice(30_000.0, 60_000.0);
+    /// let attrs = Difficulty::new().calculate(&section);
+    ///
+    /// println!("{:.2}* for {} objects", attrs.stars(), section.hit_objects.len());
+    /// ```
+    #[must_use]
+    pub fn slice(&self, start_time: f64, end_time: f64) -> Self {
+        let start = self
+            .hit_objects
+            .partition_point(|h| h.start_time < start_time);
+
+        let end = self
+            .hit_objects
+            .partition_point(|h| h.start_time < end_time)
+            .max(start);
+
+        self.slice_with_window(start..end, start_time, end_time)
+    }
+
+    /// Create a new [`Beatmap`] that only contains the hit objects within the
+    /// given index range.
+    ///
+    /// Same as [`Beatmap::slice`] with the time range spanning from the first
+    /// to the last hit object in the index range. Indices that are out of
+    /// bounds are clamped.
+    #[must_use]
+    pub fn slice_objects(&self, range: impl RangeBounds<usize>) -> Self {
+        let len = self.hit_objects.len();
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        }
+        .min(len);
+
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(end);
+
+        let objects = &self.hit_objects[start..end];
+
+        let start_time = objects.first().map_or(0.0, |h| h.start_time);
+
+        let end_time = objects
+            .iter()
+            .map(HitObject::end_time)
+            .fold(start_time, f64::max);
+
+        self.slice_with_window(start..end, start_time, end_time)
+    }
+
+    fn slice_with_window(&self, range: Range<usize>, start_time: f64, end_time: f64) -> Self {
+        let breaks = self
+            .breaks
+            .iter()
+            .filter_map(|b| {
+                let clipped = BreakPeriod {
+                    start_time: b.start_time.max(start_time),
+                    end_time: b.end_time.min(end_time),
+                };
+
+                (clipped.end_time > clipped.start_time).then_some(clipped)
+            })
+            .collect();
+
+        // Mania converts don't retain hit sounds
+        let hit_sounds = self
+            .hit_sounds
+            .get(range.clone())
+            .map_or_else(Vec::new, <[_]>::to_vec);
+
+        Self {
+            version: self.version,
+            is_convert: self.is_convert,
+            stack_leniency: self.stack_leniency,
+            mode: self.mode,
+            ar: self.ar,
+            cs: self.cs,
+            hp: self.hp,
+            od: self.od,
+            slider_multiplier: self.slider_multiplier,
+            slider_tick_rate: self.slider_tick_rate,
+            breaks,
+            timing_points: active_points(&self.timing_points, |p| p.time, start_time, end_time),
+            difficulty_points: active_points(
+                &self.difficulty_points,
+                |p| p.time,
+                start_time,
+                end_time,
+            ),
+            effect_points: active_points(&self.effect_points, |p| p.time, start_time, end_time),
+            hit_objects: self.hit_objects[range].to_vec(),
+            hit_sounds,
+            creator: self.creator.clone(),
+            beatmap_id: self.beatmap_id,
+        }
+    }
+}
+
+/// The point that is active at `start_time` and all points up to `end_time`.
+fn active_points<T: Clone>(
+    points: &[T],
+    time: impl Fn(&T) -> f64,
+    start_time: f64,
+    end_time: f64,
+) -> Vec<T> {
+    let start = points
+        .partition_point(|point| time(point) <= start_time)
+        .saturating_sub(1);
+
+    let end = points
+        .partition_point(|point| time(point) <= end_time)
+        .max(start);
+
+    points[start..end].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::control_point::{DifficultyPoint, EffectPoint, TimingPoint},
+        Difficulty,
+    };
+
+    use super::*;
+
+    #[test]
+    fn full_range_is_identity() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let map = Beatmap::from_path(path).unwrap();
+
+            assert_eq!(map.slice(f64::NEG_INFINITY, f64::INFINITY), map, "{path}");
+            assert_eq!(map.slice_objects(..).hit_objects, map.hit_objects, "{path}");
+        }
+    }
+
+    #[test]
+    fn time_range() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let sliced = map.slice(30_000.0, 60_000.0);
+
+        assert!(!sliced.hit_objects.is_empty());
+        assert!(sliced.hit_objects.len() < map.hit_objects.len());
+        assert!(sliced
+            .hit_objects
+            .iter()
+            .all(|h| (30_000.0..60_000.0).contains(&h.start_time)));
+        assert_eq!(sliced.hit_sounds.len(), sliced.hit_objects.len());
+
+        // Objects use the same control points as in the full map
+        for h in sliced.hit_objects.iter() {
+            assert_eq!(
+                sliced.timing_point_at(h.start_time),
+                map.timing_point_at(h.start_time)
+            );
+            assert_eq!(
+                sliced.difficulty_point_at(h.start_time),
+                map.difficulty_point_at(h.start_time)
+            );
+        }
+
+        let attrs = Difficulty::new().calculate(&sliced);
+        let full_attrs = Difficulty::new().calculate(&map);
+
+        assert!(attrs.max_combo() < full_attrs.max_combo());
+        assert!(attrs.stars() > 0.0);
+    }
+
+    #[test]
+    fn object_range() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let sliced = map.slice_objects(100..=199);
+        assert_eq!(sliced.hit_objects[..], map.hit_objects[100..200]);
+
+        assert!(map.slice_objects(5000..).hit_objects.is_empty());
+        assert!(map.slice_objects(50..10).hit_objects.is_empty());
+    }
+
+    #[test]
+    fn clips_control_points_and_breaks() {
+        let mut map = Beatmap::from_bytes(b"osu file format v14").unwrap();
+
+        map.timing_points = vec![
+            TimingPoint::new(0.0, 500.0),
+            TimingPoint::new(1000.0, 400.0),
+            TimingPoint::new(5000.0, 300.0),
+        ];
+        map.difficulty_points = vec![DifficultyPoint::new(3000.0, -50.0, 1.0)];
+        map.effect_points = vec![EffectPoint::new(500.0, true)];
+        map.breaks = vec![
+            BreakPeriod {
+                start_time: 1500.0,
+                end_time: 2500.0,
+            },
+            BreakPeriod {
+                start_time: 3500.0,
+                end_time: 6000.0,
+            },
+        ];
+
+        let sliced = map.slice(2000.0, 4000.0);
+
+        let timing_times: Vec<_> = sliced.timing_points.iter().map(|p| p.time).collect();
+        assert_eq!(timing_times, [1000.0]);
+        assert_eq!(sliced.difficulty_points.len(), 1);
+        assert_eq!(sliced.effect_points.len(), 1);
+
+        let breaks: Vec<_> = sliced
+            .breaks
+            .iter()
+            .map(|b| (b.start_time, b.end_time))
+            .collect();
+        assert_eq!(breaks, [(2000.0, 2500.0), (3500.0, 4000.0)]);
+    }
+}
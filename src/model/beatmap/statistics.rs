@@ -0,0 +1,177 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{catch, mania, model::mode::ConvertError, osu, taiko, Difficulty};
+
+use super::Beatmap;
+
+/// Object counts, combo, and length of a [`Beatmap`] as it is played in a
+/// given mode, computed without difficulty calculation.
+///
+/// Counts are named after their osu!standard counterparts and refer to the
+/// converted map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BeatmapStatistics {
+    /// The mode that the statistics were computed for.
+    pub mode: GameMode,
+    /// Amount of circles, dons and kats, fruits, or notes.
+    pub n_circles: u32,
+    /// Amount of sliders, drum rolls, juice streams, or hold notes.
+    pub n_sliders: u32,
+    /// Amount of spinners, swells, or banana showers.
+    pub n_spinners: u32,
+    /// Amount of slider ticks, osu!catch droplets, or osu!mania hold note
+    /// ticks.
+    ///
+    /// osu!taiko drum roll ticks are not counted since they don't give combo.
+    pub n_slider_ticks: u32,
+    /// The maximum achievable combo.
+    pub max_combo: u32,
+    /// Start time of the first hit object in ms.
+    pub first_object_time: f64,
+    /// End time of the last hit object in ms.
+    pub last_object_time: f64,
+    /// Time between the first and last hit object in ms, adjusted by the
+    /// clock rate.
+    pub total_length: f64,
+    /// [`total_length`] without breaks in ms, adjusted by the clock rate.
+    ///
+    /// [`total_length`]: BeatmapStatistics::total_length
+    pub drain_time: f64,
+}
+
+impl Beatmap {
+    /// Compute the [`BeatmapStatistics`] of this [`Beatmap`] for the given
+    /// mode.
+    ///
+    /// osu!standard maps are converted first. Only the mods and clock rate of
+    /// the [`Difficulty`] are considered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::model::mode::GameMode;
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    /// let stats = map.statistics(GameMode::Osu, &Difficulty::new().mods(64)).unwrap();
+    ///
+    /// println!("{}x | {:.0}s", stats.max_combo, stats.drain_time / 1000.0);
+    /// ```
+    pub fn statistics(
+        &self,
+        mode: GameMode,
+        difficulty: &Difficulty,
+    ) -> Result<BeatmapStatistics, ConvertError> {
+        let map = self.convert_ref(mode, difficulty.get_mods())?;
+
+        let mut stats = BeatmapStatistics {
+            mode,
+            first_object_time: map.hit_objects.first().map_or(0.0, |h| h.start_time),
+            ..Default::default()
+        };
+
+        match mode {
+            GameMode::Osu => osu::statistics(&map, &mut stats),
+            GameMode::Taiko => taiko::statistics(&map, &mut stats),
+            GameMode::Catch => catch::statistics(&map, &mut stats),
+            GameMode::Mania => mania::statistics(&map, &mut stats),
+        }
+
+        if !map.hit_objects.is_empty() {
+            let clock_rate = difficulty.get_clock_rate();
+            let total_length = stats.last_object_time - stats.first_object_time;
+
+            stats.total_length = total_length / clock_rate;
+            stats.drain_time = (total_length - map.total_break_time()).max(0.0) / clock_rate;
+        }
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{any::DifficultyAttributes, model::hit_object::HitObjectKind};
+
+    use super::*;
+
+    #[test]
+    fn max_combo_matches_attributes() {
+        let cases = [
+            ("./resources/2785319.osu", GameMode::Osu),
+            ("./resources/2785319.osu", GameMode::Taiko),
+            ("./resources/2785319.osu", GameMode::Catch),
+            ("./resources/2785319.osu", GameMode::Mania),
+            ("./resources/1028484.osu", GameMode::Taiko),
+            ("./resources/2118524.osu", GameMode::Catch),
+            ("./resources/1638954.osu", GameMode::Mania),
+        ];
+
+        for (path, mode) in cases {
+            let map = Beatmap::from_path(path).unwrap();
+            let difficulty = Difficulty::new();
+            let stats = map.statistics(mode, &difficulty).unwrap();
+
+            let converted = map.convert_ref(mode, difficulty.get_mods()).unwrap();
+            let attrs = difficulty.calculate(&converted);
+
+            assert_eq!(stats.max_combo, attrs.max_combo(), "{path} {mode:?}");
+
+            let n_objects = stats.n_circles + stats.n_sliders + stats.n_spinners;
+            assert_eq!(n_objects as usize, converted.hit_objects.len());
+
+            match attrs {
+                DifficultyAttributes::Osu(attrs) => {
+                    assert_eq!(stats.n_circles, attrs.n_circles);
+                    assert_eq!(stats.n_sliders, attrs.n_sliders);
+                    assert_eq!(stats.n_spinners, attrs.n_spinners);
+                    assert!(stats.n_slider_ticks <= attrs.n_large_ticks);
+                }
+                DifficultyAttributes::Catch(attrs) => {
+                    assert_eq!(stats.n_slider_ticks, attrs.n_droplets);
+                }
+                DifficultyAttributes::Mania(attrs) => {
+                    assert_eq!(stats.n_sliders, attrs.n_hold_notes);
+                }
+                DifficultyAttributes::Taiko(_) => assert_eq!(stats.n_slider_ticks, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let stats = map.statistics(GameMode::Osu, &Difficulty::new()).unwrap();
+
+        let last = map.hit_objects.last().unwrap();
+
+        assert!((stats.first_object_time - map.hit_objects[0].start_time).abs() < f64::EPSILON);
+        assert!(stats.last_object_time >= last.start_time);
+
+        if let HitObjectKind::Slider(_) = last.kind {
+            assert!(stats.last_object_time > last.start_time);
+        }
+
+        let total_length = stats.last_object_time - stats.first_object_time;
+        assert!((stats.total_length - total_length).abs() < f64::EPSILON);
+        assert!((stats.drain_time - (total_length - map.total_break_time())).abs() < f64::EPSILON);
+
+        let dt = map
+            .statistics(GameMode::Osu, &Difficulty::new().mods(64))
+            .unwrap();
+
+        assert_eq!(dt.max_combo, stats.max_combo);
+        assert!((dt.total_length - stats.total_length / 1.5).abs() < 1e-9);
+        assert!((dt.drain_time - stats.drain_time / 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_map() {
+        let map = Beatmap::from_bytes(b"osu file format v14").unwrap();
+        let stats = map.statistics(GameMode::Osu, &Difficulty::new()).unwrap();
+
+        assert_eq!(stats.max_combo, 0);
+        assert!(stats.total_length.abs() < f64::EPSILON);
+        assert!(stats.drain_time.abs() < f64::EPSILON);
+    }
+}
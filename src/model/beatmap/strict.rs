@@ -0,0 +1,518 @@
+use std::{borrow::Cow, error, fmt, fs, io, path::Path};
+
+use rosu_map::{
+    section::Section, util::ParseNumber, DecodeBeatmap, DecodeState, LATEST_FORMAT_VERSION,
+};
+
+use crate::model::limits::{DecodeLimits, LimitError};
+
+use super::{Beatmap, BeatmapState, ParseBeatmapError};
+
+const VERSION_PREFIX: &str = "osu file format v";
+
+impl Beatmap {
+    /// Parse a [`Beatmap`] by providing a path to a `.osu` file while
+    /// keeping track of malformed lines and limits.
+    ///
+    /// See [`Beatmap::from_bytes_with`].
+    pub fn from_path_with<P: AsRef<Path>>(
+        path: P,
+        options: &DecodeOptions,
+    ) -> Result<(Self, Vec<LineError>), DecodeError> {
+        let bytes = fs::read(path)?;
+
+        Self::from_bytes_with(&bytes, options)
+    }
+
+    /// Parse a [`Beatmap`] by providing the content of a `.osu` file as a
+    /// slice of bytes while keeping track of malformed lines and limits.
+    ///
+    /// Unlike [`Beatmap::from_bytes`], errors are returned alongside their
+    /// location. If [`DecodeOptions::strict`] is set, the first malformed
+    /// line is returned as [`DecodeError::Line`]. Otherwise, malformed lines
+    /// are skipped the same way as for [`Beatmap::from_bytes`] and collected
+    /// instead.
+    ///
+    /// Exceeding [`DecodeOptions::limits`] stops decoding and returns
+    /// [`DecodeError::Limit`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{
+    ///     model::{
+    ///         beatmap::{DecodeError, DecodeOptions},
+    ///         limits::LimitError,
+    ///     },
+    ///     Beatmap,
+    /// };
+    ///
+    /// let bytes = b"osu file format v14
+    ///
+    /// [HitObjects]
+    /// 256,192,1000,1,0,0:0:0:0:
+    /// 256,192,two thousand,1,0,0:0:0:0:";
+    ///
+    /// let (map, errors) = Beatmap::from_bytes_with(bytes, &DecodeOptions::default()).unwrap();
+    ///
+    /// assert_eq!(map.hit_objects.len(), 1);
+    /// assert_eq!(errors[0].line, 5);
+    ///
+    /// let options = DecodeOptions {
+    ///     strict: true,
+    ///     ..DecodeOptions::default()
+    /// };
+    ///
+    /// let Err(DecodeError::Line(err)) = Beatmap::from_bytes_with(bytes, &options) else {
+    ///     panic!("expected line error");
+    /// };
+    ///
+    /// assert_eq!(err.line, 5);
+    /// assert_eq!(err.content, "256,192,two thousand,1,0,0:0:0:0:");
+    ///
+    /// let mut options = DecodeOptions::default();
+    /// options.limits.max_hit_objects = 0;
+    ///
+    /// assert!(matches!(
+    ///     Beatmap::from_bytes_with(bytes, &options),
+    ///     Err(DecodeError::Limit(LimitError::HitObjects { limit: 0 })),
+    /// ));
+    /// ```
+    pub fn from_bytes_with(
+        bytes: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<(Self, Vec<LineError>), DecodeError> {
+        let Decoded { map, errors, limit } = Decoder::new(options).decode(bytes);
+
+        if let Some(err) = limit {
+            return Err(DecodeError::Limit(err));
+        }
+
+        if options.strict {
+            if let Some(err) = errors.into_iter().next() {
+                return Err(DecodeError::Line(err));
+            }
+
+            return Ok((map, Vec::new()));
+        }
+
+        Ok((map, errors))
+    }
+}
+
+/// Options for [`Beatmap::from_bytes_with`] and [`Beatmap::from_path_with`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DecodeOptions {
+    /// Whether decoding fails on the first malformed line instead of
+    /// skipping it.
+    pub strict: bool,
+    /// Caps on the content of the `.osu` file.
+    pub limits: DecodeLimits,
+}
+
+/// A [`ParseBeatmapError`] alongside its location in the `.osu` file.
+#[derive(Debug)]
+pub struct LineError {
+    /// The section of the line.
+    ///
+    /// `None` if the line precedes all sections, i.e. it is the format
+    /// version.
+    pub section: Option<Section>,
+    /// The line number, starting at `1`.
+    pub line: usize,
+    /// The content of the line.
+    pub content: String,
+    /// The error that occurred while parsing the line.
+    pub error: ParseBeatmapError,
+}
+
+impl error::Error for LineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+
+        if let Some(section) = self.section {
+            write!(f, " in [{}]", section_name(section))?;
+        }
+
+        write!(f, ": {} ({:?})", self.error, self.content)
+    }
+}
+
+/// All the ways that strictly decoding a [`Beatmap`] can fail.
+#[derive(Debug)]
+pub enum DecodeError {
+    Io(io::Error),
+    Line(LineError),
+    Limit(LimitError),
+}
+
+impl error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DecodeError::Io(err) => Some(err),
+            DecodeError::Line(err) => Some(err),
+            DecodeError::Limit(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(_) => f.write_str("failed to read beatmap"),
+            DecodeError::Line(_) => f.write_str("failed to parse beatmap"),
+            DecodeError::Limit(_) => f.write_str("beatmap exceeds limits"),
+        }
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<LineError> for DecodeError {
+    fn from(err: LineError) -> Self {
+        Self::Line(err)
+    }
+}
+
+impl From<LimitError> for DecodeError {
+    fn from(err: LimitError) -> Self {
+        Self::Limit(err)
+    }
+}
+
+const fn section_name(section: Section) -> &'static str {
+    match section {
+        Section::General => "General",
+        Section::Editor => "Editor",
+        Section::Metadata => "Metadata",
+        Section::Difficulty => "Difficulty",
+        Section::Events => "Events",
+        Section::TimingPoints => "TimingPoints",
+        Section::Colors => "Colours",
+        Section::HitObjects => "HitObjects",
+        Section::Variables => "Variables",
+        Section::CatchTheBeat => "CatchTheBeat",
+        Section::Mania => "Mania",
+    }
+}
+
+/// Reads the lines of a `.osu` file the same way as `rosu-map` while keeping
+/// track of malformed lines and limits.
+struct Decoder<'a> {
+    options: &'a DecodeOptions,
+    /// Number of the line that is being processed, starting at `1`.
+    line: usize,
+    errors: Vec<LineError>,
+    limit: Option<LimitError>,
+    n_timing_points: usize,
+    /// Whether no further lines should be processed.
+    stop: bool,
+}
+
+/// The result of [`Decoder::decode`].
+struct Decoded {
+    map: Beatmap,
+    errors: Vec<LineError>,
+    limit: Option<LimitError>,
+}
+
+impl<'a> Decoder<'a> {
+    const fn new(options: &'a DecodeOptions) -> Self {
+        Self {
+            options,
+            line: 0,
+            errors: Vec::new(),
+            limit: None,
+            n_timing_points: 0,
+            stop: false,
+        }
+    }
+
+    fn decode(mut self, bytes: &[u8]) -> Decoded {
+        let content = decode_content(bytes);
+        let mut lines = content.lines().map(str::trim_end);
+
+        // The format version is the first non-empty line. If it's missing,
+        // `rosu-map` falls back to the latest version and checks whether the
+        // line is already a section.
+        let mut version = LATEST_FORMAT_VERSION;
+        let mut section = None;
+
+        for line in lines.by_ref() {
+            self.line += 1;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            match parse_format_version(line) {
+                Ok(parsed) => version = parsed,
+                Err(error) => {
+                    section = Section::try_from_line(line);
+                    self.line_error(None, line, error);
+                }
+            }
+
+            break;
+        }
+
+        let mut state = BeatmapState::create(version);
+
+        for line in lines {
+            if self.stop {
+                break;
+            }
+
+            self.line += 1;
+
+            if line.is_empty() || line.trim_start().starts_with("//") {
+                continue;
+            }
+
+            if let Some(next) = Section::try_from_line(line) {
+                section = Some(next);
+
+                continue;
+            }
+
+            // Lines before the first section are ignored
+            if let Some(section) = section {
+                self.parse_line(&mut state, section, line);
+            }
+        }
+
+        let map = Beatmap::from(state);
+
+        let limit = match self.limit {
+            Some(err) => Some(err),
+            None => self.options.limits.check_map(&map).err(),
+        };
+
+        Decoded {
+            map,
+            errors: self.errors,
+            limit,
+        }
+    }
+
+    fn parse_line(&mut self, state: &mut BeatmapState, section: Section, line: &str) {
+        let parse_fn = match section {
+            Section::General => Beatmap::parse_general,
+            Section::Editor => Beatmap::parse_editor,
+            Section::Metadata => Beatmap::parse_metadata,
+            Section::Difficulty => Beatmap::parse_difficulty,
+            Section::Events => Beatmap::parse_events,
+            Section::TimingPoints => Beatmap::parse_timing_points,
+            Section::Colors => Beatmap::parse_colors,
+            Section::HitObjects => Beatmap::parse_hit_objects,
+            Section::Variables => Beatmap::parse_variables,
+            Section::CatchTheBeat => Beatmap::parse_catch_the_beat,
+            Section::Mania => Beatmap::parse_mania,
+        };
+
+        if let Err(error) = parse_fn(state, line) {
+            self.line_error(Some(section), line, error);
+        }
+
+        match section {
+            Section::TimingPoints => self.check_timing_points(),
+            Section::HitObjects => self.check_hit_objects(state),
+            _ => {}
+        }
+    }
+
+    fn line_error(&mut self, section: Option<Section>, line: &str, error: ParseBeatmapError) {
+        self.errors.push(LineError {
+            section,
+            line: self.line,
+            content: line.to_owned(),
+            error,
+        });
+
+        self.stop |= self.options.strict;
+    }
+
+    const fn exceed_limit(&mut self, err: LimitError) {
+        self.limit = Some(err);
+        self.stop = true;
+    }
+
+    fn check_timing_points(&mut self) {
+        self.n_timing_points += 1;
+        let limit = self.options.limits.max_timing_points;
+
+        if self.n_timing_points > limit {
+            self.exceed_limit(LimitError::TimingPoints { limit });
+        }
+    }
+
+    fn check_hit_objects(&mut self, state: &BeatmapState) {
+        let limits = &self.options.limits;
+        let hit_objects = state.hit_objects();
+
+        if hit_objects.len() > limits.max_hit_objects {
+            self.exceed_limit(LimitError::HitObjects {
+                limit: limits.max_hit_objects,
+            });
+        } else if let Some(Err(err)) = hit_objects.last().map(|h| limits.check_hit_object(h)) {
+            self.exceed_limit(err);
+        }
+    }
+}
+
+/// Decode the content based on its byte order mark, replacing invalid
+/// characters the same way as `rosu-map`.
+fn decode_content(bytes: &[u8]) -> Cow<'_, str> {
+    fn decode_utf16(bytes: &[u8], to_u16: fn([u8; 2]) -> u16) -> Cow<'_, str> {
+        let units = bytes.chunks_exact(2).map(|pair| to_u16([pair[0], pair[1]]));
+
+        char::decode_utf16(units)
+            .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect::<String>()
+            .into()
+    }
+
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8_lossy(bytes),
+    }
+}
+
+/// `rosu-map` falls back to the latest format version if the first line
+/// does not specify it so it is validated separately.
+fn parse_format_version(line: &str) -> Result<i32, ParseBeatmapError> {
+    if !line.starts_with(VERSION_PREFIX) {
+        return Err(ParseBeatmapError::UnknownFileFormat);
+    }
+
+    line.rsplit('v')
+        .next()
+        .map_or(Ok(LATEST_FORMAT_VERSION), i32::parse)
+        .map_err(ParseBeatmapError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_lenient_decoding() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let options = DecodeOptions {
+                strict: true,
+                limits: DecodeLimits::UNLIMITED,
+            };
+
+            let (strict, _) = Beatmap::from_path_with(path, &options).unwrap();
+            let lenient = Beatmap::from_path(path).unwrap();
+
+            assert_eq!(strict, lenient);
+        }
+    }
+
+    #[test]
+    fn collects_located_errors() {
+        let bytes = b"osu file format v14
+
+[General]
+Mode: 1
+
+[TimingPoints]
+0,500,4,1,0,100,1,0
+1000,500,0,1,0,100,1,0
+
+[HitObjects]
+// comment
+256,192,1000,1,0,0:0:0:0:
+256,192,1500,1,NaN,0:0:0:0:
+256,192,2000,1,0,0:0:0:0:
+256,192,2500,64,0,0:0:0:0:";
+
+        let (map, errors) = Beatmap::from_bytes_with(bytes, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(map, Beatmap::from_bytes(bytes).unwrap());
+        assert_eq!(map.hit_objects.len(), 2);
+
+        let located: Vec<_> = errors.iter().map(|err| (err.section, err.line)).collect();
+
+        assert_eq!(
+            located,
+            [
+                (Some(Section::TimingPoints), 8),
+                (Some(Section::HitObjects), 13),
+                (Some(Section::HitObjects), 15),
+            ]
+        );
+
+        assert!(matches!(errors[0].error, ParseBeatmapError::TimeSignature));
+        assert!(matches!(
+            errors[2].error,
+            ParseBeatmapError::UnknownHitObjectType
+        ));
+        assert_eq!(errors[1].content, "256,192,1500,1,NaN,0:0:0:0:");
+
+        let options = DecodeOptions {
+            strict: true,
+            ..DecodeOptions::default()
+        };
+
+        let Err(DecodeError::Line(err)) = Beatmap::from_bytes_with(bytes, &options) else {
+            panic!("expected line error");
+        };
+
+        assert_eq!(err.line, 8);
+        assert_eq!(
+            err.to_string(),
+            "line 8 in [TimingPoints]: invalid time signature, must be positive integer \
+            (\"1000,500,0,1,0,100,1,0\")"
+        );
+    }
+
+    #[test]
+    fn handles_utf16() {
+        let content = "osu file format v14\n\n[HitObjects]\n256,192,1000,1,0,0:0:0:0:\n256,192,x,1,0,0:0:0:0:";
+
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(content.encode_utf16().flat_map(u16::to_le_bytes));
+
+        let (map, errors) = Beatmap::from_bytes_with(&bytes, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(map.hit_objects.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 5);
+        assert_eq!(errors[0].content, "256,192,x,1,0,0:0:0:0:");
+    }
+
+    #[test]
+    fn requires_format_version() {
+        let bytes = b"\xEF\xBB\xBF[General]\nMode: 3";
+
+        let (map, errors) = Beatmap::from_bytes_with(bytes, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(map.mode, rosu_map::section::general::GameMode::Mania);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].section, None);
+        assert_eq!(errors[0].line, 1);
+        assert!(matches!(
+            errors[0].error,
+            ParseBeatmapError::UnknownFileFormat
+        ));
+    }
+}
@@ -1,15 +1,17 @@
 use std::cmp::Ordering;
 
-use rosu_map::section::{
-    general::GameMode,
-    hit_objects::{BorrowedCurve, CurveBuffers},
-};
+use rosu_map::section::{general::GameMode, hit_objects::SliderEvent};
 
 pub use rosu_map::{
-    section::hit_objects::{hit_samples::HitSoundType, PathControlPoint, PathType, SplineType},
+    section::hit_objects::{
+        hit_samples::HitSoundType, BorrowedCurve, CurveBuffers, PathControlPoint, PathType,
+        SplineType,
+    },
     util::Pos,
 };
 
+use crate::{Beatmap, Difficulty};
+
 /// All hitobject related data required for difficulty and performance
 /// calculation except for the [`HitSoundType`].
 #[derive(Clone, Debug, PartialEq)]
@@ -35,6 +37,39 @@ impl HitObject {
         matches!(&self.kind, HitObjectKind::Spinner(_))
     }
 
+    /// Calculate the [`SliderGeometry`] of the hitobject as it is used by
+    /// osu!standard difficulty calculation, or `None` if it is not a slider.
+    ///
+    /// The map's timing and difficulty points determine the slider's
+    /// velocity and ticks. The circle size, adjusted by the [`Difficulty`],
+    /// determines how lazily the cursor follows the slider, and mods such as
+    /// `HR` reflect the slider.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::model::hit_object::SliderBuffers;
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    /// let difficulty = Difficulty::new().mods(16); // HR
+    /// let mut bufs = SliderBuffers::default();
+    ///
+    /// for h in map.hit_objects.iter() {
+    ///     if let Some(geometry) = h.slider_geometry(&difficulty, &map, &mut bufs) {
+    ///         println!("{} path points, ends at {:?}", geometry.path.len(), geometry.end_pos);
+    ///     }
+    /// }
+    /// ```
+    pub fn slider_geometry(
+        &self,
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        bufs: &mut SliderBuffers,
+    ) -> Option<SliderGeometry> {
+        SliderGeometry::new(self, difficulty, map, bufs)
+    }
+
     /// The end time of the object.
     ///
     /// Note that this will not return the correct value for sliders.
@@ -79,15 +114,72 @@ impl Slider {
         self.repeats + 1
     }
 
-    pub(crate) fn curve<'a>(
-        &self,
-        mode: GameMode,
-        bufs: &'a mut CurveBuffers,
-    ) -> BorrowedCurve<'a> {
+    /// Calculate the path of the slider for the given mode.
+    ///
+    /// Positions are relative to the slider's head.
+    pub fn curve<'a>(&self, mode: GameMode, bufs: &'a mut CurveBuffers) -> BorrowedCurve<'a> {
         BorrowedCurve::new(mode, &self.control_points, self.expected_dist, bufs)
     }
 }
 
+/// Buffers that are re-used when calculating [`SliderGeometry`].
+#[derive(Default)]
+pub struct SliderBuffers {
+    pub(crate) curve: CurveBuffers,
+    pub(crate) ticks: Vec<SliderEvent>,
+    /// The hit circle radius alongside the [`Difficulty`] and the map's
+    /// circle size it was calculated for.
+    pub(crate) radius: Option<(Difficulty, f32, f64)>,
+}
+
+/// Geometry of a [`Slider`] as used by osu!standard difficulty calculation.
+///
+/// Positions are absolute playfield positions in map coordinates, i.e. with
+/// reflection from mods but without stacking, and times are in map time in ms.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SliderGeometry {
+    /// Points along the calculated slider path, starting at the head.
+    pub path: Vec<Pos>,
+    /// Length of the slider path.
+    pub dist: f64,
+    /// End time of the slider.
+    pub end_time: f64,
+    /// Position at the end of the slider's last span.
+    pub end_pos: Pos,
+    /// Positions of the cursor when following the slider lazily, starting at
+    /// the head and moving towards each nested object.
+    pub lazy_path: Vec<Pos>,
+    /// Position of the cursor at the end of the slider when following it
+    /// lazily.
+    pub lazy_end_pos: Pos,
+    /// Distance travelled by the cursor when following the slider lazily,
+    /// normalized to a circle radius of
+    /// [`OsuObjectGeometry::NORMALIZED_RADIUS`].
+    ///
+    /// [`OsuObjectGeometry::NORMALIZED_RADIUS`]: crate::osu::OsuObjectGeometry::NORMALIZED_RADIUS
+    pub lazy_travel_dist: f64,
+    /// Time in ms that the cursor needs to follow the slider lazily.
+    pub lazy_travel_time: f64,
+    /// Repeats, ticks, and the tail of the slider sorted by time.
+    pub nested_objects: Vec<SliderNestedObject>,
+}
+
+/// A nested object of a [`Slider`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SliderNestedObject {
+    pub kind: SliderNestedObjectKind,
+    pub pos: Pos,
+    pub start_time: f64,
+}
+
+/// The kind of a [`SliderNestedObject`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SliderNestedObjectKind {
+    Repeat,
+    Tail,
+    Tick,
+}
+
 /// A spinner.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Spinner {
@@ -99,3 +191,115 @@ pub struct Spinner {
 pub struct HoldNote {
     pub duration: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{osu::OsuObjectGeometry, Difficulty};
+
+    use super::*;
+
+    #[test]
+    fn slider_geometry() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new();
+        let mut bufs = SliderBuffers::default();
+
+        let geometries: Vec<_> = map
+            .hit_objects
+            .iter()
+            .map(|h| h.slider_geometry(&difficulty, &map, &mut bufs))
+            .collect();
+
+        let mut combo = 0;
+
+        for (h, geometry) in map.hit_objects.iter().zip(geometries.iter()) {
+            combo += 1;
+
+            let Some(geometry) = geometry else {
+                assert!(!h.is_slider());
+
+                continue;
+            };
+
+            combo += geometry.nested_objects.len() as u32;
+
+            assert_eq!(geometry.path[0], h.pos);
+            assert!(geometry.end_time > h.start_time);
+            assert!(geometry.lazy_travel_time <= geometry.end_time - h.start_time);
+            assert!(geometry
+                .nested_objects
+                .windows(2)
+                .all(|pair| pair[0].start_time <= pair[1].start_time));
+
+            let tail = geometry
+                .nested_objects
+                .iter()
+                .find(|nested| nested.kind == SliderNestedObjectKind::Tail)
+                .unwrap();
+
+            assert_eq!(tail.pos, geometry.end_pos);
+            assert_eq!(geometry.lazy_path.len(), geometry.nested_objects.len() + 1);
+            assert_eq!(geometry.lazy_path.last(), Some(&geometry.lazy_end_pos));
+        }
+
+        let attrs = Difficulty::new().calculate(&map);
+        assert_eq!(combo, attrs.max_combo());
+    }
+
+    #[test]
+    fn slider_geometry_agrees_with_difficulty() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut bufs = SliderBuffers::default();
+
+        let nomod_geometries: Vec<_> = map
+            .hit_objects
+            .iter()
+            .map(|h| h.slider_geometry(&Difficulty::new(), &map, &mut bufs))
+            .collect();
+
+        // NM, EZ, HR
+        for mods in [0, 2, 16] {
+            let difficulty = Difficulty::new().mods(mods);
+            let objects = OsuObjectGeometry::calculate(&difficulty, &map).unwrap();
+
+            for h in objects {
+                let Some(lazy_end_pos) = h.lazy_end_pos else {
+                    continue;
+                };
+
+                let geometry = map.hit_objects[h.idx]
+                    .slider_geometry(&difficulty, &map, &mut bufs)
+                    .unwrap();
+
+                let stack_offset = h.stacked_pos - geometry.path[0];
+                let expected = lazy_end_pos - stack_offset;
+
+                assert!(
+                    (geometry.lazy_end_pos.x - expected.x).abs() < 1e-3,
+                    "{mods}"
+                );
+                assert!(
+                    (geometry.lazy_end_pos.y - expected.y).abs() < 1e-3,
+                    "{mods}"
+                );
+
+                if mods == 16 {
+                    let nomod = nomod_geometries[h.idx].as_ref().unwrap();
+
+                    assert!((geometry.path[0].y - (384.0 - nomod.path[0].y)).abs() < 1e-3);
+                    assert!((geometry.end_pos.y - (384.0 - nomod.end_pos.y)).abs() < 1e-3);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn non_slider_has_no_geometry() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let circle = map.hit_objects.iter().find(|h| h.is_circle()).unwrap();
+
+        assert!(circle
+            .slider_geometry(&Difficulty::new(), &map, &mut SliderBuffers::default())
+            .is_none());
+    }
+}
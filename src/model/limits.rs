@@ -0,0 +1,794 @@
+use std::{
+    error, fmt,
+    ops::{Deref, DerefMut},
+};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{
+        beatmap::Beatmap,
+        control_point::{DifficultyPoint, TimingPoint},
+        hit_object::{CurveBuffers, HitObject, HitObjectKind, Slider},
+    },
+    util::get_precision_adjusted_beat_len,
+    Difficulty,
+};
+
+/// Caps on a [`Beatmap`] that are shared by [`DecodeLimits`] and
+/// [`CalcLimits`].
+///
+/// The [`Default`] values are generous enough for all ranked maps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeatmapLimits {
+    /// Maximum amount of hit objects.
+    pub max_hit_objects: usize,
+    /// Maximum estimated amount of nested objects such as slider ticks and
+    /// repeats across all hit objects.
+    pub max_nested_objects: usize,
+    /// Maximum amount of repeats of a single slider.
+    pub max_slider_repeats: usize,
+    /// Maximum length of a single slider in osu!pixels.
+    pub max_slider_len: f64,
+    /// Maximum amount of curve points of a single slider.
+    pub max_curve_points: usize,
+    /// Maximum time in ms of the latest hit object, including the full
+    /// duration of sliders.
+    ///
+    /// [`CalcLimits`] adjust the time by the clock rate.
+    pub max_duration: f64,
+    /// Maximum amount of timing points.
+    ///
+    /// [`DecodeLimits`] count the lines in the `[TimingPoints]` section
+    /// whereas [`CalcLimits`] count timing, difficulty, and effect points
+    /// combined.
+    pub max_timing_points: usize,
+    /// Maximum beat length in ms of a timing point.
+    pub max_beat_len: f64,
+    /// Minimum slider velocity multiplier of a difficulty point.
+    pub min_slider_velocity: f64,
+}
+
+impl BeatmapLimits {
+    /// Limits that accept everything.
+    pub const UNLIMITED: Self = Self {
+        max_hit_objects: usize::MAX,
+        max_nested_objects: usize::MAX,
+        max_slider_repeats: usize::MAX,
+        max_slider_len: f64::INFINITY,
+        max_curve_points: usize::MAX,
+        max_duration: f64::INFINITY,
+        max_timing_points: usize::MAX,
+        max_beat_len: f64::INFINITY,
+        min_slider_velocity: 0.0,
+    };
+
+    /// Create new [`BeatmapLimits`] with default values.
+    pub const fn new() -> Self {
+        Self {
+            max_hit_objects: DEFAULT_MAX_HIT_OBJECTS,
+            max_nested_objects: DEFAULT_MAX_NESTED_OBJECTS,
+            max_slider_repeats: DEFAULT_MAX_SLIDER_REPEATS,
+            max_slider_len: DEFAULT_MAX_SLIDER_LEN,
+            max_curve_points: DEFAULT_MAX_CURVE_POINTS,
+            max_duration: DEFAULT_MAX_DURATION,
+            max_timing_points: DEFAULT_MAX_TIMING_POINTS,
+            max_beat_len: DEFAULT_MAX_BEAT_LEN,
+            min_slider_velocity: DEFAULT_MIN_SLIDER_VELOCITY,
+        }
+    }
+
+    fn check_hit_object(&self, h: &HitObject) -> Result<(), LimitError> {
+        let HitObjectKind::Slider(ref slider) = h.kind else {
+            return Ok(());
+        };
+
+        if slider.repeats > self.max_slider_repeats {
+            return Err(LimitError::SliderRepeats {
+                limit: self.max_slider_repeats,
+            });
+        }
+
+        if slider.control_points.len() > self.max_curve_points {
+            return Err(LimitError::CurvePoints {
+                limit: self.max_curve_points,
+            });
+        }
+
+        if slider_len(slider) > self.max_slider_len {
+            return Err(LimitError::SliderLength {
+                limit: self.max_slider_len,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_control_points(&self, map: &Beatmap) -> Result<(), LimitError> {
+        if map
+            .timing_points
+            .iter()
+            .any(|point| point.beat_len > self.max_beat_len)
+        {
+            return Err(LimitError::BeatLength {
+                limit: self.max_beat_len,
+            });
+        }
+
+        if map
+            .difficulty_points
+            .iter()
+            .any(|point| point.slider_velocity < self.min_slider_velocity)
+        {
+            return Err(LimitError::SliderVelocity {
+                limit: self.min_slider_velocity,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check the limits that require all hit objects and control points.
+    fn check_totals(&self, map: &Beatmap, clock_rate: f64) -> Result<(), LimitError> {
+        let totals = ObjectTotals::new(map);
+
+        if totals.nested_objects > self.max_nested_objects as f64 {
+            return Err(LimitError::NestedObjects {
+                limit: self.max_nested_objects,
+            });
+        }
+
+        if totals.end_time / clock_rate > self.max_duration {
+            return Err(LimitError::Duration {
+                limit: self.max_duration,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BeatmapLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Caps on the content of a `.osu` file while decoding it.
+///
+/// Used through [`DecodeOptions`] by [`Beatmap::from_bytes_with`] and
+/// [`Beatmap::from_path_with`] to reject maliciously large maps before they
+/// are fully read into memory.
+///
+/// The individual limits are accessible through [`Deref`].
+///
+/// [`DecodeOptions`]: crate::model::beatmap::DecodeOptions
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DecodeLimits(pub BeatmapLimits);
+
+impl DecodeLimits {
+    /// Limits that accept everything.
+    pub const UNLIMITED: Self = Self(BeatmapLimits::UNLIMITED);
+
+    /// Create new [`DecodeLimits`] with default values.
+    pub const fn new() -> Self {
+        Self(BeatmapLimits::new())
+    }
+
+    pub(crate) fn check_hit_object(&self, h: &HitObject) -> Result<(), LimitError> {
+        self.0.check_hit_object(h)?;
+
+        // The full slider duration requires all timing points which might
+        // not be decoded yet so it is checked in `check_map`
+        if h.end_time() > self.max_duration {
+            return Err(LimitError::Duration {
+                limit: self.max_duration,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check the limits that require the fully decoded map.
+    pub(crate) fn check_map(&self, map: &Beatmap) -> Result<(), LimitError> {
+        self.0.check_control_points(map)?;
+
+        self.0.check_totals(map, 1.0)
+    }
+}
+
+impl Deref for DecodeLimits {
+    type Target = BeatmapLimits;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for DecodeLimits {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<BeatmapLimits> for DecodeLimits {
+    fn from(limits: BeatmapLimits) -> Self {
+        Self(limits)
+    }
+}
+
+/// Caps on a [`Beatmap`] before calculating its difficulty.
+///
+/// Used by [`Difficulty::checked_calculate`], the other `checked_*`
+/// methods of [`Difficulty`], [`Performance::checked_calculate`], or by
+/// [`CalcLimits::check`] before any other calculation, to reject maps that
+/// would take up too much time or memory.
+///
+/// The individual limits are accessible through [`Deref`].
+///
+/// [`Performance::checked_calculate`]: crate::Performance::checked_calculate
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CalcLimits(pub BeatmapLimits);
+
+impl CalcLimits {
+    /// Limits that accept everything.
+    pub const UNLIMITED: Self = Self(BeatmapLimits::UNLIMITED);
+
+    /// Create new [`CalcLimits`] with default values.
+    pub const fn new() -> Self {
+        Self(BeatmapLimits::new())
+    }
+
+    /// Check whether the map stays within the limits when calculated with
+    /// the given [`Difficulty`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::model::limits::{CalcLimits, LimitError};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// let mut limits = CalcLimits::default();
+    /// limits.max_duration = 60_000.0;
+    ///
+    /// // The map is longer than a minute
+    /// assert!(matches!(
+    ///     limits.check(&Difficulty::new(), &map),
+    ///     Err(LimitError::Duration { .. }),
+    /// ));
+    /// ```
+    pub fn check(&self, difficulty: &Difficulty, map: &Beatmap) -> Result<(), LimitError> {
+        if map.hit_objects.len() > self.max_hit_objects {
+            return Err(LimitError::HitObjects {
+                limit: self.max_hit_objects,
+            });
+        }
+
+        let n_points =
+            map.timing_points.len() + map.difficulty_points.len() + map.effect_points.len();
+
+        if n_points > self.max_timing_points {
+            return Err(LimitError::TimingPoints {
+                limit: self.max_timing_points,
+            });
+        }
+
+        self.0.check_control_points(map)?;
+
+        for h in map.hit_objects.iter() {
+            self.0.check_hit_object(h)?;
+        }
+
+        self.0.check_totals(map, difficulty.get_clock_rate())
+    }
+}
+
+impl Deref for CalcLimits {
+    type Target = BeatmapLimits;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for CalcLimits {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<BeatmapLimits> for CalcLimits {
+    fn from(limits: BeatmapLimits) -> Self {
+        Self(limits)
+    }
+}
+
+const DEFAULT_MAX_HIT_OBJECTS: usize = 100_000;
+const DEFAULT_MAX_NESTED_OBJECTS: usize = 1_000_000;
+const DEFAULT_MAX_SLIDER_REPEATS: usize = 9000;
+const DEFAULT_MAX_SLIDER_LEN: f64 = 65_536.0;
+const DEFAULT_MAX_CURVE_POINTS: usize = 10_000;
+// Three hours
+const DEFAULT_MAX_DURATION: f64 = 3.0 * 60.0 * 60.0 * 1000.0;
+const DEFAULT_MAX_TIMING_POINTS: usize = 50_000;
+// The bounds that osu! clamps beat lengths and slider velocities to
+const DEFAULT_MAX_BEAT_LEN: f64 = 60_000.0;
+const DEFAULT_MIN_SLIDER_VELOCITY: f64 = 0.1;
+
+/// Values that require iterating over all hit objects alongside their
+/// control points.
+struct ObjectTotals {
+    /// The latest end time of all hit objects, including the full duration
+    /// of sliders.
+    end_time: f64,
+    /// The estimated amount of nested objects.
+    nested_objects: f64,
+}
+
+impl ObjectTotals {
+    fn new(map: &Beatmap) -> Self {
+        let mut bufs = CurveBuffers::default();
+
+        let mut totals = Self {
+            end_time: 0.0,
+            nested_objects: 0.0,
+        };
+
+        for h in map.hit_objects.iter() {
+            let HitObjectKind::Slider(ref slider) = h.kind else {
+                totals.end_time = totals.end_time.max(h.end_time());
+
+                continue;
+            };
+
+            let timing = SliderTiming::new(h, slider, map, &mut bufs);
+            let span_count = slider.span_count() as f64;
+
+            totals.end_time = totals
+                .end_time
+                .max(h.start_time + span_count * timing.span_duration);
+
+            // Ticks are placed every `beat_len / tick_rate` ms in each span,
+            // followed by the repeat or tail
+            let ticks_per_span = timing.span_duration * map.slider_tick_rate / timing.beat_len;
+            totals.nested_objects += span_count * (ticks_per_span + 1.0);
+        }
+
+        totals
+    }
+}
+
+/// Timing of a slider, computed the same way as for osu!standard sliders.
+struct SliderTiming {
+    /// The beat length of the slider's timing point.
+    beat_len: f64,
+    /// The duration of a single span.
+    span_duration: f64,
+}
+
+impl SliderTiming {
+    fn new(h: &HitObject, slider: &Slider, map: &Beatmap, bufs: &mut CurveBuffers) -> Self {
+        const BASE_SCORING_DIST: f64 = 100.0;
+
+        let beat_len = map
+            .timing_point_at(h.start_time)
+            .map_or(TimingPoint::DEFAULT_BEAT_LEN, |point| point.beat_len);
+
+        let slider_velocity = map
+            .difficulty_point_at(h.start_time)
+            .map_or(DifficultyPoint::DEFAULT_SLIDER_VELOCITY, |point| {
+                point.slider_velocity
+            });
+
+        let velocity = BASE_SCORING_DIST * map.slider_multiplier
+            / get_precision_adjusted_beat_len(slider_velocity, beat_len);
+
+        let dist = slider.curve(GameMode::Osu, bufs).dist();
+
+        Self {
+            beat_len,
+            span_duration: dist / velocity,
+        }
+    }
+}
+
+/// The expected length of the slider or, if unavailable, the length of its
+/// control point polygon.
+fn slider_len(slider: &Slider) -> f64 {
+    if let Some(expected_dist) = slider.expected_dist {
+        return expected_dist;
+    }
+
+    slider
+        .control_points
+        .windows(2)
+        .map(|pair| f64::from(pair[0].pos.distance(pair[1].pos)))
+        .sum()
+}
+
+/// The ways that a map can exceed [`DecodeLimits`] or [`CalcLimits`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum LimitError {
+    /// Too many hit objects
+    HitObjects { limit: usize },
+    /// Too many nested objects such as slider ticks
+    NestedObjects { limit: usize },
+    /// A slider with too many repeats
+    SliderRepeats { limit: usize },
+    /// A slider that is too long
+    SliderLength { limit: f64 },
+    /// A slider with too many curve points
+    CurvePoints { limit: usize },
+    /// Hit objects that are too late
+    Duration { limit: f64 },
+    /// Too many timing points
+    TimingPoints { limit: usize },
+    /// A timing point with a beat length that is too long
+    BeatLength { limit: f64 },
+    /// A difficulty point with a slider velocity that is too low
+    SliderVelocity { limit: f64 },
+}
+
+impl error::Error for LimitError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HitObjects { limit } => write!(f, "more than {limit} hit objects"),
+            Self::NestedObjects { limit } => write!(f, "more than {limit} nested objects"),
+            Self::SliderRepeats { limit } => write!(f, "slider with more than {limit} repeats"),
+            Self::SliderLength { limit } => write!(f, "slider longer than {limit}"),
+            Self::CurvePoints { limit } => {
+                write!(f, "slider with more than {limit} curve points")
+            }
+            Self::Duration { limit } => write!(f, "hit objects after {limit}ms"),
+            Self::TimingPoints { limit } => write!(f, "more than {limit} timing points"),
+            Self::BeatLength { limit } => write!(f, "beat length longer than {limit}ms"),
+            Self::SliderVelocity { limit } => write!(f, "slider velocity lower than {limit}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::beatmap::{DecodeError, DecodeOptions},
+        Performance,
+    };
+
+    use super::*;
+
+    fn decode_strict(bytes: &[u8], limits: DecodeLimits) -> Result<Beatmap, DecodeError> {
+        let options = DecodeOptions {
+            strict: true,
+            limits,
+        };
+
+        Beatmap::from_bytes_with(bytes, &options).map(|(map, _)| map)
+    }
+
+    fn decode_lenient(bytes: &[u8], limits: DecodeLimits) -> Result<Beatmap, DecodeError> {
+        let options = DecodeOptions {
+            strict: false,
+            limits,
+        };
+
+        Beatmap::from_bytes_with(bytes, &options).map(|(map, _)| map)
+    }
+
+    #[test]
+    fn defaults_accept_resources() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let (map, _) = Beatmap::from_path_with(path, &DecodeOptions::default()).unwrap();
+
+            assert_eq!(map, Beatmap::from_path(path).unwrap());
+            assert!(CalcLimits::default()
+                .check(&Difficulty::new(), &map)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn decode_limits() {
+        let bytes = b"osu file format v14
+
+[TimingPoints]
+0,500,4,1,0,100,1,0
+1000,-50,4,1,0,100,0,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,2000,2,0,L|356:192,5,100
+256,192,9000,1,0,0:0:0:0:";
+
+        let decode = |limits| match decode_strict(bytes, limits) {
+            Ok(_) => None,
+            Err(DecodeError::Limit(err)) => Some(err),
+            Err(err) => panic!("unexpected error: {err}"),
+        };
+
+        assert_eq!(decode(DecodeLimits::default()), None);
+
+        let limits = DecodeLimits(BeatmapLimits {
+            max_timing_points: 1,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(decode(limits), Some(LimitError::TimingPoints { limit: 1 }));
+
+        let limits = DecodeLimits(BeatmapLimits {
+            max_slider_repeats: 3,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(decode(limits), Some(LimitError::SliderRepeats { limit: 3 }));
+
+        let limits = DecodeLimits(BeatmapLimits {
+            max_slider_len: 99.0,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(
+            decode(limits),
+            Some(LimitError::SliderLength { limit: 99.0 })
+        );
+
+        let limits = DecodeLimits(BeatmapLimits {
+            max_curve_points: 1,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(decode(limits), Some(LimitError::CurvePoints { limit: 1 }));
+
+        let limits = DecodeLimits(BeatmapLimits {
+            max_duration: 5000.0,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(decode(limits), Some(LimitError::Duration { limit: 5000.0 }));
+    }
+
+    #[test]
+    fn long_sliders_exceed_duration() {
+        let bytes = b"osu file format v14
+
+[TimingPoints]
+0,60000,4,1,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+256,192,2000,2,0,L|356:192,8000,100";
+
+        let limits = DecodeLimits::default();
+        let expected = LimitError::Duration {
+            limit: limits.max_duration,
+        };
+
+        assert!(matches!(
+            decode_strict(bytes, limits.clone()),
+            Err(DecodeError::Limit(err)) if err == expected
+        ));
+        assert!(matches!(
+            decode_lenient(bytes, limits),
+            Err(DecodeError::Limit(err)) if err == expected
+        ));
+
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert_eq!(
+            CalcLimits::default().check(&Difficulty::new(), &map),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn dense_slider_ticks_exceed_nested_objects() {
+        let mut content = String::from(
+            "osu file format v14
+
+[Difficulty]
+SliderTickRate:8
+
+[TimingPoints]
+0,6,4,1,0,100,1,0
+0,-1000,4,1,0,100,0,0
+
+[HitObjects]
+",
+        );
+
+        for i in 0..20 {
+            content.push_str(&format!(
+                "256,192,{},2,0,L|500:0,100,40000\n",
+                1000 + i * 1000
+            ));
+        }
+
+        let bytes = content.as_bytes();
+        let limits = DecodeLimits::default();
+        let expected = LimitError::NestedObjects {
+            limit: limits.max_nested_objects,
+        };
+
+        let mut passing = limits.clone();
+        passing.max_nested_objects = usize::MAX;
+
+        assert!(decode_strict(bytes, passing).is_ok());
+        assert!(matches!(
+            decode_strict(bytes, limits),
+            Err(DecodeError::Limit(err)) if err == expected
+        ));
+
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert_eq!(
+            CalcLimits::default().check(&Difficulty::new(), &map),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn control_point_limits() {
+        let bytes = b"osu file format v14
+
+[TimingPoints]
+0,2000,4,1,0,100,1,0
+1000,-500,4,1,0,100,0,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:";
+
+        let limits = DecodeLimits(BeatmapLimits {
+            max_beat_len: 1000.0,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert!(matches!(
+            decode_lenient(bytes, limits),
+            Err(DecodeError::Limit(err)) if err == LimitError::BeatLength { limit: 1000.0 }
+        ));
+
+        let limits = DecodeLimits(BeatmapLimits {
+            min_slider_velocity: 0.5,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert!(matches!(
+            decode_lenient(bytes, limits),
+            Err(DecodeError::Limit(err)) if err == LimitError::SliderVelocity { limit: 0.5 }
+        ));
+
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        let limits = CalcLimits(BeatmapLimits {
+            max_beat_len: 1000.0,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(
+            limits.check(&Difficulty::new(), &map),
+            Err(LimitError::BeatLength { limit: 1000.0 })
+        );
+
+        let limits = CalcLimits(BeatmapLimits {
+            min_slider_velocity: 0.5,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(
+            limits.check(&Difficulty::new(), &map),
+            Err(LimitError::SliderVelocity { limit: 0.5 })
+        );
+    }
+
+    #[test]
+    fn clock_rate_affects_duration() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let end_time = ObjectTotals::new(&map).end_time;
+
+        let limits = CalcLimits(BeatmapLimits {
+            max_duration: end_time,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert!(limits.check(&Difficulty::new(), &map).is_ok());
+        assert!(limits.check(&Difficulty::new().mods(64), &map).is_ok());
+        assert_eq!(
+            limits.check(&Difficulty::new().mods(256), &map),
+            Err(LimitError::Duration { limit: end_time })
+        );
+    }
+
+    #[test]
+    fn calc_limits() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new();
+
+        let limits = CalcLimits(BeatmapLimits {
+            max_hit_objects: 100,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(
+            difficulty.checked_calculate(&map, &limits).unwrap_err(),
+            LimitError::HitObjects { limit: 100 }
+        );
+
+        let limits = CalcLimits(BeatmapLimits {
+            max_slider_len: 100.0,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        assert_eq!(
+            limits.check(&difficulty, &map),
+            Err(LimitError::SliderLength { limit: 100.0 })
+        );
+
+        let attrs = difficulty
+            .checked_calculate(&map, &CalcLimits::default())
+            .unwrap();
+
+        assert!((attrs.stars() - difficulty.calculate(&map).stars()).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn checked_entry_points() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new();
+
+        let limits = CalcLimits(BeatmapLimits {
+            max_hit_objects: 100,
+            ..BeatmapLimits::UNLIMITED
+        });
+
+        let expected = LimitError::HitObjects { limit: 100 };
+
+        assert_eq!(
+            difficulty.checked_strains(&map, &limits).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            difficulty
+                .checked_object_strains(&map, &limits)
+                .unwrap_err(),
+            expected
+        );
+        assert!(matches!(
+            difficulty.clone().checked_gradual_difficulty(&map, &limits),
+            Err(err) if err == expected
+        ));
+        assert!(matches!(
+            difficulty.clone().checked_gradual_performance(&map, &limits),
+            Err(err) if err == expected
+        ));
+        assert_eq!(
+            Performance::new(&map)
+                .checked_calculate(&limits)
+                .unwrap_err(),
+            expected
+        );
+
+        // Attributes were already calculated
+        let attrs = difficulty.calculate(&map);
+        let pp = Performance::new(attrs.clone()).calculate().pp();
+
+        let checked = Performance::new(attrs)
+            .checked_calculate(&limits)
+            .unwrap()
+            .pp();
+
+        assert!((checked - pp).abs() < f64::EPSILON);
+    }
+}
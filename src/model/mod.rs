@@ -7,6 +7,9 @@ pub mod control_point;
 /// Hitobject related types.
 pub mod hit_object;
 
+/// Resource limits for untrusted beatmaps.
+pub mod limits;
+
 /// Gamemode related types.
 pub mod mode;
 
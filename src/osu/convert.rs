@@ -1,6 +1,9 @@
 use rosu_map::section::hit_objects::CurveBuffers;
 
-use crate::model::{beatmap::Beatmap, mods::Reflection};
+use crate::model::{
+    beatmap::{Beatmap, BeatmapStatistics},
+    mods::Reflection,
+};
 
 use super::{
     attributes::OsuDifficultyAttributes,
@@ -44,17 +47,8 @@ pub fn convert_objects(
         })
         .collect();
 
-    match reflection {
-        Reflection::None => osu_objects.iter_mut().for_each(OsuObject::finalize_nested),
-        Reflection::Vertical => osu_objects
-            .iter_mut()
-            .for_each(OsuObject::reflect_vertically),
-        Reflection::Horizontal => osu_objects
-            .iter_mut()
-            .for_each(OsuObject::reflect_horizontally),
-        Reflection::Both => osu_objects
-            .iter_mut()
-            .for_each(OsuObject::reflect_both_axes),
+    for h in osu_objects.iter_mut() {
+        h.reflect(reflection);
     }
 
     let stack_threshold = time_preempt * f64::from(map.stack_leniency);
@@ -76,6 +70,99 @@ pub fn convert_objects(
     osu_objects
 }
 
+/// Converted [`OsuObject`]s that are shared between calculations whose
+/// conversion is identical, e.g. when only the clock rate differs.
+#[derive(Default)]
+pub struct OsuObjectCache {
+    entries: Vec<CachedOsuObjects>,
+}
+
+struct CachedOsuObjects {
+    key: ConversionKey,
+    objects: Box<[OsuObject]>,
+    attrs: OsuDifficultyAttributes,
+}
+
+/// Everything that affects [`convert_objects`] for a given map.
+#[derive(PartialEq)]
+struct ConversionKey {
+    radius: u64,
+    reflection: Reflection,
+    time_preempt: u64,
+    take: usize,
+}
+
+impl OsuObjectCache {
+    /// Same as [`convert_objects`] but re-uses previously converted objects.
+    pub fn convert(
+        &mut self,
+        map: &Beatmap,
+        scaling_factor: &ScalingFactor,
+        reflection: Reflection,
+        time_preempt: f64,
+        take: usize,
+        attrs: &mut OsuDifficultyAttributes,
+    ) -> Box<[OsuObject]> {
+        let key = ConversionKey {
+            radius: scaling_factor.radius.to_bits(),
+            reflection,
+            time_preempt: time_preempt.to_bits(),
+            take,
+        };
+
+        if let Some(cached) = self.entries.iter().find(|cached| cached.key == key) {
+            attrs.n_circles = cached.attrs.n_circles;
+            attrs.n_sliders = cached.attrs.n_sliders;
+            attrs.n_large_ticks = cached.attrs.n_large_ticks;
+            attrs.n_spinners = cached.attrs.n_spinners;
+            attrs.max_combo = cached.attrs.max_combo;
+
+            return cached.objects.clone();
+        }
+
+        let objects = convert_objects(map, scaling_factor, reflection, time_preempt, take, attrs);
+
+        self.entries.push(CachedOsuObjects {
+            key,
+            objects: objects.clone(),
+            attrs: attrs.clone(),
+        });
+
+        objects
+    }
+
+    /// The amount of distinct conversions.
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+pub fn statistics(map: &Beatmap, stats: &mut BeatmapStatistics) {
+    let mut curve_bufs = CurveBuffers::default();
+    let mut ticks_buf = Vec::new();
+
+    for h in map.hit_objects.iter() {
+        let h = OsuObject::new(h, map, &mut curve_bufs, &mut ticks_buf);
+        stats.max_combo += 1;
+        stats.last_object_time = stats.last_object_time.max(h.end_time());
+
+        match h.kind {
+            OsuObjectKind::Circle => stats.n_circles += 1,
+            OsuObjectKind::Slider(ref slider) => {
+                stats.n_sliders += 1;
+                stats.max_combo += slider.nested_objects.len() as u32;
+                stats.n_slider_ticks += slider
+                    .nested_objects
+                    .iter()
+                    .filter(|nested| matches!(nested.kind, NestedSliderObjectKind::Tick))
+                    .count() as u32;
+            }
+            OsuObjectKind::Spinner(_) => stats.n_spinners += 1,
+        }
+    }
+}
+
 const STACK_DISTANCE: f32 = 3.0;
 
 fn stacking(hit_objects: &mut [OsuObject], stack_threshold: f64) {
@@ -0,0 +1,295 @@
+use std::pin::Pin;
+
+use rosu_map::{section::general::GameMode, util::Pos};
+
+use crate::{
+    model::{
+        hit_object::{
+            HitObject, HitObjectKind, SliderBuffers, SliderGeometry, SliderNestedObject,
+            SliderNestedObjectKind,
+        },
+        mode::ConvertError,
+        mods::Reflection,
+    },
+    osu::{
+        convert::convert_objects,
+        object::{NestedSliderObjectKind, OsuObject, OsuObjectKind},
+        PLAYFIELD_BASE_SIZE,
+    },
+    Beatmap, Difficulty,
+};
+
+use super::{object::OsuDifficultyObject, start_times, DifficultyValues, OsuDifficultySetup};
+
+/// The kind of an osu!standard hit object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OsuObjectGeometryKind {
+    Circle,
+    Slider,
+    Spinner,
+}
+
+/// Geometric features of a single hit object as used by the difficulty
+/// calculation.
+///
+/// Positions include stacking and mod adjustments such as HR's reflection.
+/// Distances are normalized to a circle radius of
+/// [`OsuObjectGeometry::NORMALIZED_RADIUS`] and durations are in real time,
+/// i.e. adjusted by the clock rate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuObjectGeometry {
+    /// Index of the hit object in the map.
+    pub idx: usize,
+    /// The kind of the hit object.
+    pub kind: OsuObjectGeometryKind,
+    /// Start time of the hit object in map time in ms.
+    pub start_time: f64,
+    /// Time since the previous hit object in ms.
+    pub delta_time: f64,
+    /// [`delta_time`](Self::delta_time) capped at a minimum of 25ms.
+    pub strain_time: f64,
+    /// Position of the hit object including its stack offset.
+    pub stacked_pos: Pos,
+    /// Position of the cursor at the end of a slider when following it
+    /// lazily, or `None` if the hit object is not a slider.
+    pub lazy_end_pos: Option<Pos>,
+    /// Normalized distance from the previous object's end cursor position.
+    pub lazy_jump_dist: f64,
+    /// Normalized minimum distance from the previous object.
+    pub min_jump_dist: f64,
+    /// Time in ms for the minimum jump distance.
+    pub min_jump_time: f64,
+    /// Normalized distance travelled by the cursor within a slider.
+    pub travel_dist: f64,
+    /// Time in ms the cursor travels within a slider.
+    pub travel_time: f64,
+    /// Angle in radians between the previous two hit objects and this one.
+    pub angle: Option<f64>,
+}
+
+impl OsuObjectGeometry {
+    /// The circle radius that distances are normalized to.
+    pub const NORMALIZED_RADIUS: f32 = OsuDifficultyObject::NORMALIZED_RADIUS;
+
+    /// Calculate the geometric features of all hit objects of a map.
+    ///
+    /// The first hit object has no predecessor and is thus not included.
+    /// [`Difficulty::passed_objects`] and [`Difficulty::passed_time`] are
+    /// respected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::osu::OsuObjectGeometry;
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    /// let objects = OsuObjectGeometry::calculate(&Difficulty::new().mods(16), &map).unwrap();
+    ///
+    /// for h in objects.iter().filter(|h| h.angle.is_some_and(|angle| angle < 0.5)) {
+    ///     println!("Sharp angle at {}ms: {:.2}", h.start_time, h.lazy_jump_dist);
+    /// }
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Vec<Self>, ConvertError> {
+        let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(&map));
+        let take = difficulty.get_passed_objects();
+
+        let OsuDifficultySetup {
+            scaling_factor,
+            mut attrs,
+            time_preempt,
+            ..
+        } = OsuDifficultySetup::new(difficulty, &map);
+
+        let mut osu_objects = convert_objects(
+            &map,
+            &scaling_factor,
+            difficulty.get_mods().reflection(),
+            time_preempt,
+            take,
+            &mut attrs,
+        );
+
+        let diff_objects = DifficultyValues::create_difficulty_objects(
+            difficulty,
+            &scaling_factor,
+            osu_objects.iter_mut().map(Pin::new),
+        );
+
+        // The first hit object has no difficulty object
+        let take_diff_objects = take.min(map.hit_objects.len()).saturating_sub(1);
+
+        let geometry = diff_objects
+            .iter()
+            .take(take_diff_objects)
+            .map(|h| Self::new(h, &map))
+            .collect();
+
+        Ok(geometry)
+    }
+
+    fn new(h: &OsuDifficultyObject<'_>, map: &Beatmap) -> Self {
+        let idx = h.idx + 1;
+
+        let (kind, lazy_end_pos) = match h.base.kind {
+            OsuObjectKind::Circle => (OsuObjectGeometryKind::Circle, None),
+            OsuObjectKind::Slider(ref slider) => {
+                (OsuObjectGeometryKind::Slider, Some(slider.lazy_end_pos))
+            }
+            OsuObjectKind::Spinner(_) => (OsuObjectGeometryKind::Spinner, None),
+        };
+
+        Self {
+            idx,
+            kind,
+            start_time: map.hit_objects[idx].start_time,
+            delta_time: h.delta_time,
+            strain_time: h.strain_time,
+            stacked_pos: h.base.stacked_pos(),
+            lazy_end_pos,
+            lazy_jump_dist: h.lazy_jump_dist,
+            min_jump_dist: h.min_jump_dist,
+            min_jump_time: h.min_jump_time,
+            travel_dist: h.travel_dist,
+            travel_time: h.travel_time,
+            angle: h.angle,
+        }
+    }
+}
+
+impl SliderGeometry {
+    pub(crate) fn new(
+        h: &HitObject,
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        bufs: &mut SliderBuffers,
+    ) -> Option<Self> {
+        let HitObjectKind::Slider(ref slider) = h.kind else {
+            return None;
+        };
+
+        let reflection = difficulty.get_mods().reflection();
+
+        let (path, dist) = {
+            let curve = slider.curve(GameMode::Osu, &mut bufs.curve);
+
+            let path = curve
+                .path()
+                .iter()
+                .map(|&pos| reflect_pos(h.pos + pos, reflection))
+                .collect();
+
+            (path, curve.dist())
+        };
+
+        let mut osu_object = OsuObject::new(h, map, &mut bufs.curve, &mut bufs.ticks);
+        osu_object.reflect(reflection);
+
+        let pos = osu_object.pos;
+
+        if let OsuObjectKind::Slider(ref mut slider) = osu_object.kind {
+            slider.lazy_end_pos += pos;
+        }
+
+        let radius = hit_circle_radius(bufs, difficulty, map);
+        let mut lazy_path = vec![pos];
+
+        OsuDifficultyObject::track_slider_cursor(Pin::new(&mut osu_object), radius, |pos| {
+            lazy_path.push(pos);
+        });
+
+        let OsuObjectKind::Slider(ref slider) = osu_object.kind else {
+            return None;
+        };
+
+        let nested_objects = slider
+            .nested_objects
+            .iter()
+            .map(|nested| SliderNestedObject {
+                kind: match nested.kind {
+                    NestedSliderObjectKind::Repeat => SliderNestedObjectKind::Repeat,
+                    NestedSliderObjectKind::Tail => SliderNestedObjectKind::Tail,
+                    NestedSliderObjectKind::Tick => SliderNestedObjectKind::Tick,
+                },
+                pos: nested.pos,
+                start_time: nested.start_time,
+            })
+            .collect();
+
+        Some(Self {
+            path,
+            dist,
+            end_time: slider.end_time,
+            end_pos: osu_object.end_pos(),
+            lazy_path,
+            lazy_end_pos: slider.lazy_end_pos,
+            lazy_travel_dist: f64::from(slider.lazy_travel_dist),
+            lazy_travel_time: slider.lazy_travel_time,
+            nested_objects,
+        })
+    }
+}
+
+/// The hit circle radius, only recalculated if the [`Difficulty`] or the
+/// map's circle size changed since the last call.
+fn hit_circle_radius(bufs: &mut SliderBuffers, difficulty: &Difficulty, map: &Beatmap) -> f64 {
+    match bufs.radius {
+        Some((ref cached, cs, radius))
+            if cached == difficulty && cs.to_bits() == map.cs.to_bits() =>
+        {
+            radius
+        }
+        _ => {
+            let radius = OsuDifficultySetup::new(difficulty, map)
+                .scaling_factor
+                .radius;
+
+            bufs.radius = Some((difficulty.clone(), map.cs, radius));
+
+            radius
+        }
+    }
+}
+
+fn reflect_pos(pos: Pos, reflection: Reflection) -> Pos {
+    match reflection {
+        Reflection::None => pos,
+        Reflection::Vertical => Pos::new(pos.x, PLAYFIELD_BASE_SIZE.y - pos.y),
+        Reflection::Horizontal => Pos::new(PLAYFIELD_BASE_SIZE.x - pos.x, pos.y),
+        Reflection::Both => PLAYFIELD_BASE_SIZE - pos,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hard_rock_reflects_positions() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let nomod = OsuObjectGeometry::calculate(&Difficulty::new(), &map).unwrap();
+        let hr = OsuObjectGeometry::calculate(&Difficulty::new().mods(16), &map).unwrap();
+
+        assert_eq!(nomod.len(), map.hit_objects.len() - 1);
+        assert_eq!(nomod.len(), hr.len());
+
+        let reflected = nomod
+            .iter()
+            .zip(hr.iter())
+            .filter(|(a, b)| (a.stacked_pos.y - b.stacked_pos.y).abs() > f32::EPSILON)
+            .count();
+
+        assert!(reflected > 0);
+        assert!(nomod
+            .iter()
+            .all(|h| h.strain_time >= h.delta_time.min(25.0)));
+
+        let partial =
+            OsuObjectGeometry::calculate(&Difficulty::new().passed_objects(50), &map).unwrap();
+
+        assert_eq!(partial.len(), 49);
+        assert_eq!(partial[..], nomod[..49]);
+    }
+}
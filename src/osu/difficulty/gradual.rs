@@ -9,6 +9,7 @@ use crate::{
         convert::convert_objects,
         object::{OsuObject, OsuObjectKind},
     },
+    util::checkpoints::Checkpoints,
     Beatmap, Difficulty,
 };
 
@@ -62,6 +63,8 @@ pub struct OsuGradualDifficulty {
     // `osu_objects` will immediately invalidate `diff_objects`.
     diff_objects: Box<[OsuDifficultyObject<'static>]>,
     osu_objects: OsuObjects,
+    start_times: Box<[f64]>,
+    checkpoints: Checkpoints<Snapshot>,
     // Additional safety measure that this type can't be cloned as it would
     // invalidate `diff_objects`.
     _not_clonable: NotClonable,
@@ -69,8 +72,16 @@ pub struct OsuGradualDifficulty {
 
 struct NotClonable;
 
+struct Snapshot {
+    attrs: OsuDifficultyAttributes,
+    skills: OsuSkills,
+}
+
 impl OsuGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!standard maps.
+    ///
+    /// Objects past [`Difficulty::passed_objects`] or [`Difficulty::passed_time`]
+    /// are not processed.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
         let mods = difficulty.get_mods();
         let map = map.convert_ref(GameMode::Osu, mods)?;
@@ -82,6 +93,14 @@ impl OsuGradualDifficulty {
             time_preempt,
         } = OsuDifficultySetup::new(&difficulty, &map);
 
+        let mut start_times: Vec<f64> = super::start_times(&map).collect();
+
+        let take = difficulty
+            .resolve_passed_time(|| start_times.iter().copied())
+            .get_passed_objects();
+
+        start_times.truncate(take);
+
         let osu_objects = convert_objects(
             &map,
             &scaling_factor,
@@ -103,15 +122,23 @@ impl OsuGradualDifficulty {
 
         let mut osu_objects = OsuObjects::new(osu_objects);
 
-        let diff_objects = DifficultyValues::create_difficulty_objects(
+        let mut diff_objects = DifficultyValues::create_difficulty_objects(
             &difficulty,
             &scaling_factor,
             osu_objects.iter_mut(),
         );
 
+        // The first hit object has no difficulty object
+        diff_objects.truncate(take.saturating_sub(1));
+
         let skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
         let diff_objects = extend_lifetime(diff_objects.into_boxed_slice());
 
+        let checkpoints = Checkpoints::new(Snapshot {
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
@@ -119,10 +146,129 @@ impl OsuGradualDifficulty {
             skills,
             diff_objects,
             osu_objects,
+            start_times: start_times.into_boxed_slice(),
+            checkpoints,
             _not_clonable: NotClonable,
         })
     }
 
+    /// Store a snapshot of the current state every `interval` hit objects.
+    ///
+    /// Snapshots allow [`seek`] to go backwards without processing all
+    /// previous hit objects again. Smaller intervals make seeking faster but
+    /// require more memory. An interval of `0` disables snapshots.
+    ///
+    /// [`seek`]: OsuGradualDifficulty::seek
+    #[must_use]
+    pub const fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the state after processing the first `idx` hit objects and
+    /// return the resulting attributes.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Values of `idx` larger than the amount of
+    /// hit objects will process all hit objects.
+    ///
+    /// Returns `None` if `idx` is `0` or the map has no hit objects.
+    ///
+    /// [`checkpoint_interval`]: OsuGradualDifficulty::checkpoint_interval
+    pub fn seek(&mut self, idx: usize) -> Option<OsuDifficultyAttributes> {
+        let Some(n) = idx.checked_sub(1) else {
+            self.restore(0);
+
+            return None;
+        };
+
+        if n < self.idx {
+            self.restore(n);
+        }
+
+        self.nth(n - self.idx)
+    }
+
+    /// Process all hit objects that started within the given amount of
+    /// milliseconds and return the resulting attributes.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further hit object started within that time.
+    pub fn passed_time(&mut self, passed_time: f64) -> Option<OsuDifficultyAttributes> {
+        self.remaining_until(passed_time)
+            .checked_sub(1)
+            .and_then(|n| self.nth(n))
+    }
+
+    /// Amount of hit objects that remain to be processed until the given
+    /// amount of milliseconds passed.
+    pub(crate) fn remaining_until(&self, passed_time: f64) -> usize {
+        self.difficulty
+            .count_passed(&self.start_times, passed_time)
+            .saturating_sub(self.idx)
+    }
+
+    /// The hit object that will be processed next.
+    pub(crate) fn next_object(&self) -> Option<&OsuObject> {
+        if self.idx < self.start_times.len() {
+            self.osu_objects.get(self.idx)
+        } else {
+            None
+        }
+    }
+
+    fn restore(&mut self, idx: usize) {
+        let (idx, snapshot) = self.checkpoints.latest_until(idx);
+
+        self.idx = idx;
+        self.attrs.clone_from(&snapshot.attrs);
+        self.skills = snapshot.skills.clone();
+    }
+
+    fn record_checkpoint(&mut self) {
+        let Self {
+            idx,
+            attrs,
+            skills,
+            checkpoints,
+            ..
+        } = self;
+
+        checkpoints.record(*idx, || Snapshot {
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+        });
+    }
+
+    /// Process the next `take` hit objects without evaluating them.
+    fn advance(&mut self, mut take: usize) {
+        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
+
+        // The first note has no difficulty object
+        if self.idx == 0 && take > 0 {
+            take -= 1;
+            self.idx += 1;
+        }
+
+        let mut aim = Skill::new(&mut self.skills.aim, &self.diff_objects);
+        let mut aim_no_sliders = Skill::new(&mut self.skills.aim_no_sliders, &self.diff_objects);
+        let mut speed = Skill::new(&mut self.skills.speed, &self.diff_objects);
+        let mut flashlight = Skill::new(&mut self.skills.flashlight, &self.diff_objects);
+
+        for curr in skip_iter.take(take) {
+            aim.process(curr);
+            aim_no_sliders.process(curr);
+            speed.process(curr);
+            flashlight.process(curr);
+
+            Self::increment_combo(curr.base, &mut self.attrs);
+            self.idx += 1;
+        }
+    }
+
     fn increment_combo(h: &OsuObject, attrs: &mut OsuDifficultyAttributes) {
         attrs.max_combo += 1;
 
@@ -164,11 +310,12 @@ impl Iterator for OsuGradualDifficulty {
             Skill::new(&mut self.skills.flashlight, &self.diff_objects).process(curr);
 
             Self::increment_combo(curr.base, &mut self.attrs);
-        } else if self.osu_objects.is_empty() {
+        } else if self.start_times.is_empty() {
             return None;
         }
 
         self.idx += 1;
+        self.record_checkpoint();
 
         let mut attrs = self.attrs.clone();
 
@@ -198,29 +345,13 @@ impl Iterator for OsuGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
-
         let mut take = cmp::min(n, self.len().saturating_sub(1));
 
-        // The first note has no difficulty object
-        if self.idx == 0 && take > 0 {
-            take -= 1;
-            self.idx += 1;
-        }
-
-        let mut aim = Skill::new(&mut self.skills.aim, &self.diff_objects);
-        let mut aim_no_sliders = Skill::new(&mut self.skills.aim_no_sliders, &self.diff_objects);
-        let mut speed = Skill::new(&mut self.skills.speed, &self.diff_objects);
-        let mut flashlight = Skill::new(&mut self.skills.flashlight, &self.diff_objects);
-
-        for curr in skip_iter.take(take) {
-            aim.process(curr);
-            aim_no_sliders.process(curr);
-            speed.process(curr);
-            flashlight.process(curr);
-
-            Self::increment_combo(curr.base, &mut self.attrs);
-            self.idx += 1;
+        while take > 0 {
+            let step = cmp::min(take, self.checkpoints.steps_until_due(self.idx));
+            self.advance(step);
+            self.record_checkpoint();
+            take -= step;
         }
 
         self.next()
@@ -229,7 +360,8 @@ impl Iterator for OsuGradualDifficulty {
 
 impl ExactSizeIterator for OsuGradualDifficulty {
     fn len(&self) -> usize {
-        self.diff_objects.len() + 1 - self.idx
+        // Only contains the start times of objects that will be processed
+        self.start_times.len() - self.idx
     }
 }
 
@@ -248,8 +380,8 @@ mod osu_objects {
             Self { objects }
         }
 
-        pub(super) const fn is_empty(&self) -> bool {
-            self.objects.is_empty()
+        pub(super) fn get(&self, idx: usize) -> Option<&OsuObject> {
+            self.objects.get(idx)
         }
 
         pub(super) fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = Pin<&mut OsuObject>> {
@@ -310,4 +442,75 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn passed_time() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = OsuGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        for passed_time in [5000.0, 12345.0, 30000.0] {
+            let expected = difficulty
+                .clone()
+                .passed_time(passed_time)
+                .calculate_for_mode::<Osu>(&map)
+                .unwrap();
+
+            assert_eq!(gradual.passed_time(passed_time), Some(expected));
+        }
+
+        assert!(gradual.passed_time(30000.0).is_none());
+    }
+
+    #[test]
+    fn stops_at_difficulty_passed_time() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64).passed_time(12345.0);
+        let gradual = OsuGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        let expected = difficulty.calculate_for_mode::<Osu>(&map).unwrap();
+
+        assert_eq!(gradual.last(), Some(expected));
+    }
+
+    #[test]
+    fn passed_time_caps_remaining_objects() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().passed_time(12345.0);
+        let gradual = OsuGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.remaining_until(f64::MAX), gradual.len());
+
+        // Nothing passed before the first object
+        let difficulty = Difficulty::new().passed_time(-1.0);
+        let mut gradual = OsuGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.next(), None);
+    }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new();
+        let mut gradual = OsuGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .checkpoint_interval(50);
+
+        let len = gradual.len();
+
+        for idx in [len / 2, 10, len, 1, 2, 137, 0, 99, 100] {
+            let expected = idx.checked_sub(1).and_then(|n| {
+                OsuGradualDifficulty::new(difficulty.clone(), &map)
+                    .unwrap()
+                    .nth(n)
+            });
+
+            assert_eq!(gradual.seek(idx), expected, "idx={idx}");
+        }
+    }
 }
@@ -10,7 +10,7 @@ use crate::{
     any::difficulty::{skills::Skill, Difficulty},
     model::{beatmap::BeatmapAttributes, mode::ConvertError, mods::GameMods},
     osu::{
-        convert::convert_objects,
+        convert::{convert_objects, OsuObjectCache},
         difficulty::{object::OsuDifficultyObject, scaling_factor::ScalingFactor},
         object::OsuObject,
         performance::PERFORMANCE_BASE_MULTIPLIER,
@@ -22,6 +22,7 @@ use self::skills::OsuSkills;
 
 use super::attributes::OsuDifficultyAttributes;
 
+pub mod geometry;
 pub mod gradual;
 mod object;
 pub mod scaling_factor;
@@ -70,6 +71,63 @@ pub fn difficulty(
     Ok(attrs)
 }
 
+/// Calculate the difficulty attributes for multiple mods that only differ in
+/// how the processed skills are evaluated, e.g. `RX` or `AP` variants.
+///
+/// Skills are processed only once w.r.t. the mods of the given [`Difficulty`]
+/// and converted objects are taken from the cache if possible.
+pub fn difficulty_for_mods<'m>(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    mods: impl IntoIterator<Item = &'m GameMods>,
+    cache: &mut OsuObjectCache,
+) -> Result<Vec<OsuDifficultyAttributes>, ConvertError> {
+    let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+    let DifficultyValues {
+        skills:
+            OsuSkills {
+                aim,
+                aim_no_sliders,
+                speed,
+                flashlight,
+            },
+        attrs,
+    } = DifficultyValues::calculate_with_cache(difficulty, &map, cache).0;
+
+    let aim_difficulty_value = aim.difficulty_value();
+    let aim_no_sliders_difficulty_value = aim_no_sliders.difficulty_value();
+    let speed_relevant_note_count = speed.relevant_note_count();
+    let speed_difficulty_value = speed.difficulty_value();
+    let flashlight_difficulty_value = flashlight.difficulty_value();
+
+    let all_attrs = mods
+        .into_iter()
+        .map(|mods| {
+            let mut attrs = attrs.clone();
+
+            DifficultyValues::eval(
+                &mut attrs,
+                mods,
+                &aim_difficulty_value,
+                &aim_no_sliders_difficulty_value,
+                &speed_difficulty_value,
+                speed_relevant_note_count,
+                flashlight_difficulty_value,
+            );
+
+            attrs
+        })
+        .collect();
+
+    Ok(all_attrs)
+}
+
+/// Start times of all objects that count towards the amount of passed objects.
+pub(crate) fn start_times(map: &Beatmap) -> impl Iterator<Item = f64> + '_ {
+    map.hit_objects.iter().map(|h| h.start_time)
+}
+
 pub struct OsuDifficultySetup {
     scaling_factor: ScalingFactor,
     map_attrs: BeatmapAttributes,
@@ -108,6 +166,41 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        Self::calculate_with_objects(difficulty, map).0
+    }
+
+    /// Same as [`DifficultyValues::calculate`] but all skills also store the
+    /// strain value of each object.
+    pub fn calculate_object_strains(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, map, None, true).0
+    }
+
+    /// Same as [`DifficultyValues::calculate`] but also returns the converted
+    /// [`OsuObject`]s.
+    pub fn calculate_with_objects(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+    ) -> (Self, Box<[OsuObject]>) {
+        Self::calculate_with(difficulty, map, None, false)
+    }
+
+    /// Same as [`DifficultyValues::calculate_with_objects`] but converts
+    /// objects through the cache.
+    pub fn calculate_with_cache(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        cache: &mut OsuObjectCache,
+    ) -> (Self, Box<[OsuObject]>) {
+        Self::calculate_with(difficulty, map, Some(cache), false)
+    }
+
+    fn calculate_with(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        cache: Option<&mut OsuObjectCache>,
+        object_strains: bool,
+    ) -> (Self, Box<[OsuObject]>) {
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(map));
         let mods = difficulty.get_mods();
         let take = difficulty.get_passed_objects();
 
@@ -118,14 +211,24 @@ impl DifficultyValues {
             time_preempt,
         } = OsuDifficultySetup::new(difficulty, map);
 
-        let mut osu_objects = convert_objects(
-            map,
-            &scaling_factor,
-            mods.reflection(),
-            time_preempt,
-            take,
-            &mut attrs,
-        );
+        let mut osu_objects = match cache {
+            Some(cache) => cache.convert(
+                map,
+                &scaling_factor,
+                mods.reflection(),
+                time_preempt,
+                take,
+                &mut attrs,
+            ),
+            None => convert_objects(
+                map,
+                &scaling_factor,
+                mods.reflection(),
+                time_preempt,
+                take,
+                &mut attrs,
+            ),
+        };
 
         let osu_object_iter = osu_objects.iter_mut().map(Pin::new);
 
@@ -134,6 +237,10 @@ impl DifficultyValues {
 
         let mut skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
 
+        if object_strains {
+            skills.enable_object_strains();
+        }
+
         {
             let mut aim = Skill::new(&mut skills.aim, &diff_objects);
             let mut aim_no_sliders = Skill::new(&mut skills.aim_no_sliders, &diff_objects);
@@ -151,7 +258,7 @@ impl DifficultyValues {
             }
         }
 
-        Self { skills, attrs }
+        (Self { skills, attrs }, osu_objects)
     }
 
     /// Process the difficulty values and store the results in `attrs`.
@@ -170,9 +170,16 @@ impl<'a> OsuDifficultyObject<'a> {
 
     /// The [`Pin<&mut OsuObject>`](std::pin::Pin) denotes that the object will
     /// be mutated but not moved.
-    pub fn compute_slider_cursor_pos(
+    pub fn compute_slider_cursor_pos(h: Pin<&mut OsuObject>, radius: f64) -> Pin<&mut OsuObject> {
+        Self::track_slider_cursor(h, radius, |_| {})
+    }
+
+    /// Same as [`OsuDifficultyObject::compute_slider_cursor_pos`] but passes
+    /// each intermediate cursor position to `on_move`.
+    pub fn track_slider_cursor(
         mut h: Pin<&mut OsuObject>,
         radius: f64,
+        mut on_move: impl FnMut(Pos),
     ) -> Pin<&mut OsuObject> {
         let pos = h.pos;
         let stack_offset = h.stack_offset;
@@ -214,6 +221,8 @@ impl<'a> OsuDifficultyObject<'a> {
                 slider.lazy_travel_dist += curr_movement_len as f32;
             }
 
+            on_move(curr_cursor_pos);
+
             if i == nested.len() {
                 slider.lazy_end_pos = curr_cursor_pos;
             }
@@ -34,6 +34,10 @@ impl Aim {
         self.inner.get_curr_strain_peaks().strains()
     }
 
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.inner.into_object_strains()
+    }
+
     pub fn difficulty_value(self) -> UsedOsuStrainSkills<DifficultyValue> {
         Self::static_difficulty_value(self.inner)
     }
@@ -104,7 +108,7 @@ impl<'a> Skill<'a, Aim> {
         self.inner.curr_strain +=
             AimEvaluator::evaluate_diff_of(curr, self.diff_objects, self.inner.with_sliders)
                 * SKILL_MULTIPLIER;
-        self.inner.inner.object_strains.push(self.inner.curr_strain);
+        self.inner.inner.save_object_strain(self.inner.curr_strain);
 
         self.inner.curr_strain
     }
@@ -13,6 +13,7 @@ use crate::{
 const SKILL_MULTIPLIER: f64 = 0.05512;
 const STRAIN_DECAY_BASE: f64 = 0.15;
 
+#[derive(Clone)]
 pub struct Flashlight {
     curr_strain: f64,
     has_hidden_mod: bool,
@@ -36,6 +37,14 @@ impl Flashlight {
         self.inner.get_curr_strain_peaks()
     }
 
+    pub fn enable_object_strains(&mut self) {
+        self.inner.enable_object_strains();
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.into_object_strains()
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
@@ -98,6 +107,8 @@ impl<'a> Skill<'a, Flashlight> {
         }
 
         let strain_value_at = self.strain_value_at(curr);
+
+        self.inner.inner.save_object_strain(strain_value_at);
         *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
     }
 
@@ -113,6 +124,7 @@ impl<'a> Skill<'a, Flashlight> {
     }
 }
 
+#[derive(Clone)]
 struct FlashlightEvaluator {
     scaling_factor: f64,
     time_preempt: f64,
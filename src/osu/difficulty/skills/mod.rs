@@ -12,6 +12,7 @@ pub mod flashlight;
 pub mod speed;
 pub mod strain;
 
+#[derive(Clone)]
 pub struct OsuSkills {
     pub aim: Aim,
     pub aim_no_sliders: Aim,
@@ -54,4 +55,10 @@ impl OsuSkills {
             flashlight,
         }
     }
+
+    /// Store the strain value of each object for all skills.
+    pub fn enable_object_strains(&mut self) {
+        // Aim and speed always store their object strains
+        self.flashlight.enable_object_strains();
+    }
 }
@@ -44,6 +44,10 @@ impl Speed {
         self.inner.get_curr_strain_peaks().strains()
     }
 
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.inner.into_object_strains()
+    }
+
     pub fn difficulty_value(self) -> UsedOsuStrainSkills<DifficultyValue> {
         Self::static_difficulty_value(self.inner)
     }
@@ -64,15 +68,20 @@ impl Speed {
 
     pub fn relevant_note_count(&self) -> f64 {
         self.inner
+            .inner
             .object_strains
             .iter()
             .copied()
             .max_by(f64::total_cmp)
             .filter(|&n| n > 0.0)
             .map_or(0.0, |max_strain| {
-                self.inner.object_strains.iter().fold(0.0, |sum, strain| {
-                    sum + (1.0 + (-(strain / max_strain * 12.0 - 6.0)).exp()).recip()
-                })
+                self.inner
+                    .inner
+                    .object_strains
+                    .iter()
+                    .fold(0.0, |sum, strain| {
+                        sum + (1.0 + (-(strain / max_strain * 12.0 - 6.0)).exp()).recip()
+                    })
             })
     }
 }
@@ -136,7 +145,7 @@ impl<'a> Skill<'a, Speed> {
             RhythmEvaluator::evaluate_diff_of(curr, self.diff_objects, self.inner.hit_window);
 
         let total_strain = self.inner.curr_strain * self.inner.curr_rhythm;
-        self.inner.inner.object_strains.push(total_strain);
+        self.inner.inner.save_object_strain(total_strain);
 
         total_strain
     }
@@ -1,18 +1,20 @@
+use std::mem;
+
 use crate::{any::difficulty::skills::StrainSkill, util::strains_vec::StrainsVec};
 
 #[derive(Clone)]
 pub struct OsuStrainSkill {
-    pub object_strains: Vec<f64>,
     pub inner: StrainSkill,
 }
 
 impl Default for OsuStrainSkill {
     fn default() -> Self {
-        Self {
-            // mean=406.72 | median=307
-            object_strains: Vec::with_capacity(256),
-            inner: StrainSkill::default(),
-        }
+        let mut inner = StrainSkill::default();
+
+        // Object strains are required to count difficult strains
+        inner.enable_object_strains();
+
+        Self { inner }
     }
 }
 
@@ -31,10 +33,16 @@ impl OsuStrainSkill {
         self.inner.start_new_section_from(initial_strain);
     }
 
-    pub fn get_curr_strain_peaks(self) -> UsedOsuStrainSkills<StrainsVec> {
+    pub fn save_object_strain(&mut self, strain: f64) {
+        self.inner.save_object_strain(strain);
+    }
+
+    pub fn get_curr_strain_peaks(mut self) -> UsedOsuStrainSkills<StrainsVec> {
+        let object_strains = mem::take(&mut self.inner.object_strains);
+
         UsedOsuStrainSkills {
             value: self.inner.get_curr_strain_peaks(),
-            object_strains: self.object_strains,
+            object_strains,
         }
     }
 
@@ -89,6 +97,12 @@ pub struct UsedOsuStrainSkills<T> {
     object_strains: Vec<f64>,
 }
 
+impl<T> UsedOsuStrainSkills<T> {
+    pub fn object_strains(&self) -> &[f64] {
+        &self.object_strains
+    }
+}
+
 impl UsedOsuStrainSkills<DifficultyValue> {
     pub const fn difficulty_value(&self) -> f64 {
         self.value.0
@@ -5,21 +5,33 @@ use crate::{
         beatmap::Beatmap,
         mode::{ConvertError, IGameMode},
     },
-    Difficulty,
+    Difficulty, GameMods,
 };
 
 pub use self::{
     attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
-    difficulty::gradual::OsuGradualDifficulty,
-    performance::{gradual::OsuGradualPerformance, OsuPerformance},
+    difficulty::{
+        geometry::{OsuObjectGeometry, OsuObjectGeometryKind},
+        gradual::OsuGradualDifficulty,
+    },
+    pattern::{OsuPattern, OsuPatternKind, OsuPatternSummary, OsuPatterns},
+    performance::{
+        gradual::OsuGradualPerformance,
+        judgement::{OsuJudgement, OsuJudgementError, OsuJudgementPerformance},
+        probabilistic::{OsuPerformanceDistribution, OsuPlayerModel, OsuProbabilisticPerformance},
+        OsuPerformance,
+    },
     score_state::{OsuScoreOrigin, OsuScoreState},
-    strains::OsuStrains,
+    strains::{OsuHardestSections, OsuObjectStrain, OsuStrains},
 };
 
+pub(crate) use self::convert::{statistics, OsuObjectCache};
+
 mod attributes;
 mod convert;
 mod difficulty;
 mod object;
+mod pattern;
 mod performance;
 mod score_state;
 mod strains;
@@ -31,6 +43,17 @@ const PLAYFIELD_BASE_SIZE: Pos = Pos::new(512.0, 384.0);
 /// [`GameMode::Osu`]: rosu_map::section::general::GameMode::Osu
 pub struct Osu;
 
+impl Osu {
+    pub(crate) fn difficulty_for_mods<'m>(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        mods: impl IntoIterator<Item = &'m GameMods>,
+        cache: &mut OsuObjectCache,
+    ) -> Result<Vec<OsuDifficultyAttributes>, ConvertError> {
+        difficulty::difficulty_for_mods(difficulty, map, mods, cache)
+    }
+}
+
 impl IGameMode for Osu {
     type DifficultyAttributes = OsuDifficultyAttributes;
     type Strains = OsuStrains;
@@ -12,6 +12,7 @@ use crate::{
     model::{
         control_point::{DifficultyPoint, TimingPoint},
         hit_object::{HitObject, HitObjectKind, HoldNote, Slider, Spinner},
+        mods::Reflection,
     },
     util::{get_precision_adjusted_beat_len, sort},
     Beatmap,
@@ -19,6 +20,7 @@ use crate::{
 
 use super::PLAYFIELD_BASE_SIZE;
 
+#[derive(Clone)]
 pub struct OsuObject {
     pub pos: Pos,
     pub start_time: f64,
@@ -118,6 +120,17 @@ impl OsuObject {
         }
     }
 
+    /// Apply the given reflection or, if there is none, only finalize the
+    /// positions of nested objects.
+    pub fn reflect(&mut self, reflection: Reflection) {
+        match reflection {
+            Reflection::None => self.finalize_nested(),
+            Reflection::Vertical => self.reflect_vertically(),
+            Reflection::Horizontal => self.reflect_horizontally(),
+            Reflection::Both => self.reflect_both_axes(),
+        }
+    }
+
     pub fn finalize_nested(&mut self) {
         if let OsuObjectKind::Slider(ref mut slider) = self.kind {
             for nested in slider.nested_objects.iter_mut() {
@@ -171,12 +184,14 @@ impl OsuObject {
     }
 }
 
+#[derive(Clone)]
 pub enum OsuObjectKind {
     Circle,
     Slider(OsuSlider),
     Spinner(Spinner),
 }
 
+#[derive(Clone)]
 pub struct OsuSlider {
     pub end_time: f64,
     pub lazy_end_pos: Pos,
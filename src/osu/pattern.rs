@@ -0,0 +1,332 @@
+use std::{f64::consts::FRAC_PI_4, ops::RangeInclusive};
+
+use crate::{model::mode::ConvertError, util::runs, Beatmap, Difficulty};
+
+use super::{
+    difficulty::geometry::{OsuObjectGeometry, OsuObjectGeometryKind},
+    strains::{object_strains, OsuObjectStrain},
+};
+
+/// Maximum strain time in ms between two objects of a stream or burst.
+const STREAM_MAX_STRAIN_TIME: f64 = 125.0;
+/// Minimum amount of objects in a stream; less than that is a burst.
+const STREAM_MIN_OBJECTS: usize = 8;
+/// Minimum amount of objects in a burst.
+const BURST_MIN_OBJECTS: usize = 3;
+/// Minimum average normalized distance between objects of a spaced stream.
+const SPACED_STREAM_MIN_DIST: f64 = 100.0;
+
+/// Maximum strain time in ms between two objects of a jump pattern.
+const JUMP_MAX_STRAIN_TIME: f64 = 300.0;
+/// Minimum normalized distance between two objects of a jump pattern.
+const JUMP_MIN_DIST: f64 = 150.0;
+/// Minimum amount of jumps in a row to be considered a pattern.
+const JUMP_MIN_JUMPS: usize = 2;
+/// Angles below this value indicate the cursor moving back and forth.
+const ALT_JUMP_MAX_ANGLE: f64 = FRAC_PI_4;
+
+/// Minimum amount of sliders in a row for a slider-heavy section.
+const SLIDER_HEAVY_MIN_OBJECTS: usize = 4;
+
+/// The kind of an [`OsuPattern`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OsuPatternKind {
+    /// Many quickly consecutive objects that overlap or are close together.
+    Stream,
+    /// Few quickly consecutive objects.
+    Burst,
+    /// Many quickly consecutive objects that are spaced apart.
+    SpacedStream,
+    /// Consecutive objects with large distances inbetween.
+    Jumps,
+    /// Jumps where the cursor moves back and forth.
+    AltJumps,
+    /// Many sliders in a row.
+    SliderHeavy,
+}
+
+/// A range of hit objects that form a pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuPattern {
+    /// The kind of pattern.
+    pub kind: OsuPatternKind,
+    /// Index of the first hit object of the pattern.
+    pub first_object: usize,
+    /// Index of the last hit object of the pattern.
+    pub last_object: usize,
+    /// Start time of the first hit object in map time in ms.
+    pub start_time: f64,
+    /// Start time of the last hit object in map time in ms.
+    pub end_time: f64,
+    /// The highest aim strain within the pattern.
+    pub aim: f64,
+    /// The highest speed strain within the pattern.
+    pub speed: f64,
+}
+
+impl OsuPattern {
+    /// The amount of hit objects in the pattern.
+    pub const fn n_objects(&self) -> usize {
+        self.last_object - self.first_object + 1
+    }
+}
+
+/// Summary of the patterns of a map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuPatternSummary {
+    /// The amount of considered hit objects.
+    pub n_objects: usize,
+    /// The amount of hit objects within streams.
+    pub stream_objects: usize,
+    /// The amount of hit objects within bursts.
+    pub burst_objects: usize,
+    /// The amount of hit objects within spaced streams.
+    pub spaced_stream_objects: usize,
+    /// The amount of hit objects within jump patterns.
+    pub jump_objects: usize,
+    /// The amount of hit objects within alternating jump patterns.
+    pub alt_jump_objects: usize,
+    /// The amount of hit objects within slider-heavy sections.
+    pub slider_heavy_objects: usize,
+    /// The amount of hit objects of the longest stream or spaced stream.
+    pub longest_stream: usize,
+}
+
+impl OsuPatternSummary {
+    /// The amount of hit objects within patterns of the given kind.
+    pub const fn objects(&self, kind: OsuPatternKind) -> usize {
+        match kind {
+            OsuPatternKind::Stream => self.stream_objects,
+            OsuPatternKind::Burst => self.burst_objects,
+            OsuPatternKind::SpacedStream => self.spaced_stream_objects,
+            OsuPatternKind::Jumps => self.jump_objects,
+            OsuPatternKind::AltJumps => self.alt_jump_objects,
+            OsuPatternKind::SliderHeavy => self.slider_heavy_objects,
+        }
+    }
+
+    /// The percentage of hit objects within patterns of the given kind.
+    pub fn percentage(&self, kind: OsuPatternKind) -> f64 {
+        if self.n_objects == 0 {
+            return 0.0;
+        }
+
+        100.0 * self.objects(kind) as f64 / self.n_objects as f64
+    }
+
+    fn add(&mut self, pattern: &OsuPattern) {
+        let n_objects = pattern.n_objects();
+
+        let count = match pattern.kind {
+            OsuPatternKind::Stream => &mut self.stream_objects,
+            OsuPatternKind::Burst => &mut self.burst_objects,
+            OsuPatternKind::SpacedStream => &mut self.spaced_stream_objects,
+            OsuPatternKind::Jumps => &mut self.jump_objects,
+            OsuPatternKind::AltJumps => &mut self.alt_jump_objects,
+            OsuPatternKind::SliderHeavy => &mut self.slider_heavy_objects,
+        };
+
+        *count += n_objects;
+
+        if matches!(
+            pattern.kind,
+            OsuPatternKind::Stream | OsuPatternKind::SpacedStream
+        ) {
+            self.longest_stream = self.longest_stream.max(n_objects);
+        }
+    }
+}
+
+/// The patterns of an osu!standard map.
+///
+/// Patterns are detected based on the [`OsuObjectGeometry`] of hit objects.
+/// Patterns of different kinds may overlap, e.g. a slider-heavy section may
+/// also contain jumps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuPatterns {
+    /// All detected patterns, sorted by their first hit object.
+    pub patterns: Vec<OsuPattern>,
+    /// Summary of all patterns.
+    pub summary: OsuPatternSummary,
+}
+
+impl OsuPatterns {
+    /// Detect the patterns of a map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::osu::{OsuPatternKind, OsuPatterns};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    /// let patterns = OsuPatterns::calculate(&Difficulty::new(), &map).unwrap();
+    ///
+    /// println!(
+    ///     "{:.2}% stream objects, longest stream: {}",
+    ///     patterns.summary.percentage(OsuPatternKind::Stream),
+    ///     patterns.summary.longest_stream,
+    /// );
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let geometry = OsuObjectGeometry::calculate(difficulty, map)?;
+        let strains = object_strains(difficulty, map)?;
+
+        Ok(Self::new(&geometry, &strains, map))
+    }
+
+    fn new(geometry: &[OsuObjectGeometry], strains: &[OsuObjectStrain], map: &Beatmap) -> Self {
+        let mut patterns = Vec::new();
+
+        let pattern = |kind, objects: RangeInclusive<usize>, edges: RangeInclusive<usize>| {
+            let (aim, speed) = strains[edges]
+                .iter()
+                .fold((0.0, 0.0), |(aim, speed): (f64, f64), strain| {
+                    (aim.max(strain.aim), speed.max(strain.speed))
+                });
+
+            OsuPattern {
+                kind,
+                first_object: *objects.start(),
+                last_object: *objects.end(),
+                start_time: map.hit_objects[*objects.start()].start_time,
+                end_time: map.hit_objects[*objects.end()].start_time,
+                aim,
+                speed,
+            }
+        };
+
+        // Each geometry entry describes the movement from the previous object
+        let is_stream_edge = |h: &OsuObjectGeometry| {
+            h.strain_time <= STREAM_MAX_STRAIN_TIME && h.kind != OsuObjectGeometryKind::Spinner
+        };
+
+        for edges in runs(geometry, is_stream_edge) {
+            let n_objects = edges.end() - edges.start() + 2;
+
+            let kind = if n_objects >= STREAM_MIN_OBJECTS {
+                let dist_sum: f64 = geometry[edges.clone()]
+                    .iter()
+                    .map(|h| h.lazy_jump_dist)
+                    .sum();
+
+                if dist_sum / (n_objects - 1) as f64 >= SPACED_STREAM_MIN_DIST {
+                    OsuPatternKind::SpacedStream
+                } else {
+                    OsuPatternKind::Stream
+                }
+            } else if n_objects >= BURST_MIN_OBJECTS {
+                OsuPatternKind::Burst
+            } else {
+                continue;
+            };
+
+            let objects = geometry[*edges.start()].idx - 1..=geometry[*edges.end()].idx;
+            patterns.push(pattern(kind, objects, edges));
+        }
+
+        let is_jump_edge = |h: &OsuObjectGeometry| {
+            h.strain_time > STREAM_MAX_STRAIN_TIME
+                && h.strain_time <= JUMP_MAX_STRAIN_TIME
+                && h.lazy_jump_dist >= JUMP_MIN_DIST
+        };
+
+        for edges in runs(geometry, is_jump_edge) {
+            let n_jumps = edges.end() - edges.start() + 1;
+
+            if n_jumps < JUMP_MIN_JUMPS {
+                continue;
+            }
+
+            let n_alternating = geometry[edges.clone()]
+                .iter()
+                .filter(|h| h.angle.is_some_and(|angle| angle < ALT_JUMP_MAX_ANGLE))
+                .count();
+
+            let kind = if 2 * n_alternating > n_jumps {
+                OsuPatternKind::AltJumps
+            } else {
+                OsuPatternKind::Jumps
+            };
+
+            let objects = geometry[*edges.start()].idx - 1..=geometry[*edges.end()].idx;
+            patterns.push(pattern(kind, objects, edges));
+        }
+
+        let is_slider = |h: &OsuObjectGeometry| h.kind == OsuObjectGeometryKind::Slider;
+
+        for edges in runs(geometry, is_slider) {
+            if edges.end() - edges.start() + 1 < SLIDER_HEAVY_MIN_OBJECTS {
+                continue;
+            }
+
+            let objects = geometry[*edges.start()].idx..=geometry[*edges.end()].idx;
+            patterns.push(pattern(OsuPatternKind::SliderHeavy, objects, edges));
+        }
+
+        patterns.sort_by_key(|pattern| pattern.first_object);
+
+        let mut summary = OsuPatternSummary {
+            n_objects: geometry
+                .last()
+                .map_or(map.hit_objects.len().min(1), |h| h.idx + 1),
+            ..Default::default()
+        };
+
+        for pattern in patterns.iter() {
+            summary.add(pattern);
+        }
+
+        Self { patterns, summary }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_patterns() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let patterns = OsuPatterns::calculate(&Difficulty::new(), &map).unwrap();
+
+        assert_eq!(patterns.summary.n_objects, map.hit_objects.len());
+        assert!(!patterns.patterns.is_empty());
+
+        for pattern in patterns.patterns.iter() {
+            assert!(pattern.first_object < pattern.last_object);
+            assert!(pattern.start_time <= pattern.end_time);
+        }
+
+        let counted: usize = patterns.patterns.iter().map(OsuPattern::n_objects).sum();
+        let summed = [
+            OsuPatternKind::Stream,
+            OsuPatternKind::Burst,
+            OsuPatternKind::SpacedStream,
+            OsuPatternKind::Jumps,
+            OsuPatternKind::AltJumps,
+            OsuPatternKind::SliderHeavy,
+        ]
+        .into_iter()
+        .map(|kind| patterns.summary.objects(kind))
+        .sum();
+
+        assert_eq!(counted, summed);
+    }
+
+    #[test]
+    fn half_time_reduces_dense_patterns() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let nomod = OsuPatterns::calculate(&Difficulty::new(), &map).unwrap();
+        let ht = OsuPatterns::calculate(&Difficulty::new().mods(256), &map).unwrap();
+
+        let dense = |patterns: &OsuPatterns| {
+            patterns.summary.stream_objects
+                + patterns.summary.burst_objects
+                + patterns.summary.spaced_stream_objects
+        };
+
+        assert!(dense(&ht) <= dense(&nomod));
+    }
+}
@@ -0,0 +1,426 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use crate::{model::mode::ConvertError, osu::object::OsuObjectKind, Beatmap, Difficulty};
+
+use super::{gradual::OsuGradualPerformance, OsuPerformanceAttributes, OsuScoreState};
+
+/// A single judgement of a hit object or one of its nested objects.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OsuJudgement {
+    /// The hit object was hit with a 300.
+    Great,
+    /// The hit object was hit with a 100.
+    Ok,
+    /// The hit object was hit with a 50.
+    Meh,
+    /// The hit object was missed.
+    Miss,
+    /// A slider tick or repeat was hit.
+    SliderTick,
+    /// A slider tick or repeat was missed.
+    LargeTickMiss,
+    /// A slider end was hit.
+    SliderEnd,
+}
+
+/// Error type when an [`OsuJudgement`] does not fit the current hit object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OsuJudgementError {
+    /// All hit objects have already been judged
+    NoRemainingObjects,
+    /// The judgement requires a slider but the current hit object is none
+    NotASlider { judgement: OsuJudgement },
+    /// All ticks and repeats of the current slider have already been judged
+    TooManyTicks { max: usize },
+    /// The judgement of a nested object precedes the judgement of the
+    /// current slider's head
+    SliderHeadNotJudged { judgement: OsuJudgement },
+}
+
+impl Error for OsuJudgementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for OsuJudgementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            OsuJudgementError::NoRemainingObjects => {
+                f.write_str("All hit objects have already been judged")
+            }
+            OsuJudgementError::NotASlider { judgement } => {
+                write!(
+                    f,
+                    "Judgement {judgement:?} requires the hit object to be a slider"
+                )
+            }
+            OsuJudgementError::TooManyTicks { max } => {
+                write!(f, "The current slider only has {max} ticks and repeats")
+            }
+            OsuJudgementError::SliderHeadNotJudged { judgement } => {
+                write!(
+                    f,
+                    "Judgement {judgement:?} requires the slider head to be judged first"
+                )
+            }
+        }
+    }
+}
+
+/// Gradually calculate the performance attributes of an osu!standard map
+/// based on individual judgements.
+///
+/// Unlike [`OsuGradualPerformance`], this type keeps track of the score state
+/// itself. Judgements are passed in the order they occur in-game, i.e. the
+/// judgement of a slider's head comes first, followed by its nested objects
+/// [`OsuJudgement::SliderTick`] and [`OsuJudgement::LargeTickMiss`], and lastly
+/// [`OsuJudgement::SliderEnd`] which completes the slider.
+///
+/// If the slider end is missed, the slider is completed by the judgement of
+/// the next hit object or by [`OsuJudgementPerformance::finish_slider`].
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::osu::{OsuJudgement, OsuJudgementPerformance};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let difficulty = Difficulty::new().mods(64); // DT
+/// let mut gradual = OsuJudgementPerformance::new(difficulty, &map).unwrap();
+///
+/// // The first eight objects are circles
+/// for _ in 0..8 {
+///     let attrs = gradual.judge(OsuJudgement::Great).unwrap();
+///     assert!(attrs.is_some());
+/// }
+///
+/// // The ninth object is a slider which is completed by its end
+/// assert!(gradual.judge(OsuJudgement::Ok).unwrap().is_none());
+/// let attrs = gradual.judge(OsuJudgement::SliderEnd).unwrap().unwrap();
+/// println!("PP: {}", attrs.pp);
+///
+/// // Circles don't have nested objects
+/// assert!(gradual.judge(OsuJudgement::SliderTick).is_err());
+///
+/// println!("{:?}", gradual.state());
+/// ```
+pub struct OsuJudgementPerformance {
+    gradual: OsuGradualPerformance,
+    classic_slider_acc: bool,
+    state: OsuScoreState,
+    curr_combo: u32,
+    ticks: usize,
+    slider_head: bool,
+}
+
+impl OsuJudgementPerformance {
+    /// Create a new judgement-based performance calculator for osu!standard
+    /// maps.
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let lazer = difficulty.get_lazer();
+        let classic_slider_acc = lazer && difficulty.get_mods().no_slider_head_acc(lazer);
+        let gradual = OsuGradualPerformance::new(difficulty, map)?;
+
+        Ok(Self {
+            gradual,
+            classic_slider_acc,
+            state: OsuScoreState::new(),
+            curr_combo: 0,
+            ticks: 0,
+            slider_head: false,
+        })
+    }
+
+    /// Process a judgement of the current hit object.
+    ///
+    /// Returns the performance attributes once a hit object is completed,
+    /// i.e. for [`OsuJudgement::Great`], [`OsuJudgement::Ok`],
+    /// [`OsuJudgement::Meh`], and [`OsuJudgement::Miss`] on circles and
+    /// spinners, and for [`OsuJudgement::SliderEnd`] on sliders. Judging the
+    /// next hit object while the current slider's end was not judged
+    /// completes the slider first.
+    ///
+    /// Judgements that don't complete a hit object only update the state and
+    /// return `Ok(None)`.
+    pub fn judge(
+        &mut self,
+        judgement: OsuJudgement,
+    ) -> Result<Option<OsuPerformanceAttributes>, OsuJudgementError> {
+        let h = self
+            .gradual
+            .next_object()
+            .ok_or(OsuJudgementError::NoRemainingObjects)?;
+
+        // Amount of ticks and repeats if the hit object is a slider
+        let max_ticks = match h.kind {
+            OsuObjectKind::Slider(ref slider) => Some(slider.large_tick_count()),
+            OsuObjectKind::Circle | OsuObjectKind::Spinner(_) => None,
+        };
+
+        match (judgement, max_ticks) {
+            (
+                OsuJudgement::SliderTick | OsuJudgement::LargeTickMiss | OsuJudgement::SliderEnd,
+                None,
+            ) => Err(OsuJudgementError::NotASlider { judgement }),
+            (
+                OsuJudgement::SliderTick | OsuJudgement::LargeTickMiss | OsuJudgement::SliderEnd,
+                Some(_),
+            ) if !self.slider_head => Err(OsuJudgementError::SliderHeadNotJudged { judgement }),
+            (OsuJudgement::SliderTick | OsuJudgement::LargeTickMiss, Some(max)) => {
+                if self.ticks == max {
+                    return Err(OsuJudgementError::TooManyTicks { max });
+                }
+
+                self.ticks += 1;
+
+                if judgement == OsuJudgement::SliderTick {
+                    self.state.large_tick_hits += 1;
+                    self.increment_combo();
+                } else {
+                    self.curr_combo = 0;
+                }
+
+                Ok(None)
+            }
+            (OsuJudgement::SliderEnd, Some(_)) => {
+                self.state.slider_end_hits += 1;
+                self.increment_combo();
+
+                Ok(self.finish_object())
+            }
+            (
+                OsuJudgement::Great | OsuJudgement::Ok | OsuJudgement::Meh | OsuJudgement::Miss,
+                _,
+            ) if self.slider_head => {
+                // The judgement belongs to the next hit object
+                if self.gradual.len() <= 1 {
+                    return Err(OsuJudgementError::NoRemainingObjects);
+                }
+
+                let attrs = self.finish_object();
+
+                self.judge(judgement).map(|next| next.or(attrs))
+            }
+            (OsuJudgement::Great | OsuJudgement::Ok | OsuJudgement::Meh, _) => {
+                match judgement {
+                    OsuJudgement::Great => self.state.n300 += 1,
+                    OsuJudgement::Ok => self.state.n100 += 1,
+                    _ => self.state.n50 += 1,
+                }
+
+                self.increment_combo();
+
+                if max_ticks.is_none() {
+                    return Ok(self.finish_object());
+                }
+
+                // Slider heads count as large ticks for classic slider accuracy
+                if self.classic_slider_acc {
+                    self.state.large_tick_hits += 1;
+                }
+
+                self.slider_head = true;
+
+                Ok(None)
+            }
+            (OsuJudgement::Miss, _) => {
+                self.state.misses += 1;
+                self.curr_combo = 0;
+
+                if max_ticks.is_none() {
+                    return Ok(self.finish_object());
+                }
+
+                self.slider_head = true;
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Complete the current slider without judging its end, i.e. the slider
+    /// end counts as missed.
+    ///
+    /// Only required for the last hit object of a map since judging the next
+    /// hit object completes the slider as well. Returns `None` if the head
+    /// of the current hit object has not been judged or if it is not a
+    /// slider.
+    pub fn finish_slider(&mut self) -> Option<OsuPerformanceAttributes> {
+        if self.slider_head {
+            self.finish_object()
+        } else {
+            None
+        }
+    }
+
+    /// The current score state, including judgements of nested objects of
+    /// the current hit object.
+    pub const fn state(&self) -> &OsuScoreState {
+        &self.state
+    }
+
+    /// Returns the amount of remaining objects.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.gradual.len()
+    }
+
+    fn finish_object(&mut self) -> Option<OsuPerformanceAttributes> {
+        self.ticks = 0;
+        self.slider_head = false;
+
+        self.gradual.next(self.state)
+    }
+
+    fn increment_combo(&mut self) {
+        self.curr_combo += 1;
+        self.state.max_combo = self.state.max_combo.max(self.curr_combo);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osu::OsuPerformance;
+
+    use super::*;
+
+    #[test]
+    fn full_combo_matches_ss() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut gradual = OsuJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        let mut last = None;
+
+        while let Some(h) = gradual.gradual.next_object() {
+            let OsuObjectKind::Slider(ref slider) = h.kind else {
+                last = gradual.judge(OsuJudgement::Great).unwrap();
+
+                continue;
+            };
+
+            let n_ticks = slider.large_tick_count();
+
+            // Same order as in-game: head, ticks and repeats, end
+            assert!(gradual.judge(OsuJudgement::Great).unwrap().is_none());
+
+            for _ in 0..n_ticks {
+                assert!(gradual.judge(OsuJudgement::SliderTick).unwrap().is_none());
+            }
+
+            last = gradual.judge(OsuJudgement::SliderEnd).unwrap();
+            assert!(last.is_some());
+        }
+
+        let expected = OsuPerformance::from(&map).calculate().unwrap();
+
+        assert_eq!(last.unwrap(), expected);
+        assert_eq!(
+            gradual.judge(OsuJudgement::Great),
+            Err(OsuJudgementError::NoRemainingObjects)
+        );
+    }
+
+    #[test]
+    fn slider_in_game_order() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut gradual = OsuJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        // Skip to the first slider with ticks
+        loop {
+            let h = gradual.gradual.next_object().unwrap();
+
+            match h.kind {
+                OsuObjectKind::Slider(ref slider) if slider.large_tick_count() > 0 => break,
+                OsuObjectKind::Slider(_) => {
+                    gradual.judge(OsuJudgement::Great).unwrap();
+                    gradual.judge(OsuJudgement::SliderEnd).unwrap();
+                }
+                OsuObjectKind::Circle | OsuObjectKind::Spinner(_) => {
+                    gradual.judge(OsuJudgement::Great).unwrap();
+                }
+            }
+        }
+
+        let combo = gradual.curr_combo;
+
+        assert!(gradual.judge(OsuJudgement::Great).unwrap().is_none());
+        assert_eq!(gradual.curr_combo, combo + 1);
+
+        // Missing a tick after hitting the head breaks the combo
+        assert!(gradual
+            .judge(OsuJudgement::LargeTickMiss)
+            .unwrap()
+            .is_none());
+        assert_eq!(gradual.curr_combo, 0);
+
+        let len = gradual.len();
+        let attrs = gradual.judge(OsuJudgement::SliderEnd).unwrap();
+
+        assert!(attrs.is_some());
+        assert_eq!(gradual.len(), len - 1);
+        assert_eq!(gradual.curr_combo, 1);
+        assert_eq!(gradual.state().max_combo, combo + 1);
+        assert_eq!(gradual.state().large_tick_hits, 0);
+    }
+
+    #[test]
+    fn next_object_completes_slider() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut gradual = OsuJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        for _ in 0..8 {
+            gradual.judge(OsuJudgement::Great).unwrap();
+        }
+
+        assert!(gradual.judge(OsuJudgement::Great).unwrap().is_none());
+
+        let len = gradual.len();
+
+        // The slider end was missed so judging the next object completes it
+        let attrs = gradual.judge(OsuJudgement::Great).unwrap();
+
+        assert!(attrs.is_some());
+        assert!(gradual.len() < len);
+        assert_eq!(gradual.state().n300, 10);
+        assert_eq!(gradual.state().slider_end_hits, 0);
+        assert!(gradual.finish_slider().is_none());
+    }
+
+    #[test]
+    fn validates_nested_judgements() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut gradual = OsuJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        assert_eq!(
+            gradual.judge(OsuJudgement::SliderEnd),
+            Err(OsuJudgementError::NotASlider {
+                judgement: OsuJudgement::SliderEnd
+            })
+        );
+
+        for _ in 0..8 {
+            gradual.judge(OsuJudgement::Great).unwrap();
+        }
+
+        assert_eq!(
+            gradual.judge(OsuJudgement::SliderTick),
+            Err(OsuJudgementError::SliderHeadNotJudged {
+                judgement: OsuJudgement::SliderTick
+            })
+        );
+
+        gradual.judge(OsuJudgement::Miss).unwrap();
+
+        assert_eq!(gradual.state().max_combo, 8);
+        assert_eq!(gradual.state().misses, 1);
+        assert!(gradual.finish_slider().is_some());
+    }
+}
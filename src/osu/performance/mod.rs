@@ -6,7 +6,11 @@ use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, Performance},
     catch::CatchPerformance,
     mania::ManiaPerformance,
-    model::{mode::ConvertError, mods::GameMods},
+    model::{
+        limits::{CalcLimits, LimitError},
+        mode::ConvertError,
+        mods::GameMods,
+    },
     taiko::TaikoPerformance,
     util::{float_ext::FloatExt, map_or_attrs::MapOrAttrs},
     Beatmap,
@@ -20,6 +24,8 @@ use super::{
 };
 
 pub mod gradual;
+pub mod judgement;
+pub mod probabilistic;
 
 /// Performance calculator on osu!standard maps.
 #[derive(Clone, Debug, PartialEq)]
@@ -239,6 +245,20 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Amount of passed time in milliseconds for partial plays, e.g. a fail.
+    ///
+    /// Only takes effect if the difficulty attributes still have to be
+    /// calculated, i.e. if a beatmap was provided instead of attributes.
+    ///
+    /// See [`Difficulty::passed_time`] for more information.
+    ///
+    /// [`Difficulty::passed_time`]: crate::Difficulty::passed_time
+    pub fn passed_time(mut self, passed_time: f64) -> Self {
+        self.difficulty = self.difficulty.passed_time(passed_time);
+
+        self
+    }
+
     /// Adjust the clock rate used in the calculation.
     ///
     /// If none is specified, it will take the clock rate based on the mods
@@ -734,6 +754,10 @@ impl<'map> OsuPerformance<'map> {
         }
     }
 
+    pub(crate) fn check_limits(&self, limits: &CalcLimits) -> Result<(), LimitError> {
+        self.map_or_attrs.check_limits(&self.difficulty, limits)
+    }
+
     #[allow(clippy::result_large_err)]
     pub(crate) fn try_convert_map(
         map_or_attrs: MapOrAttrs<'map, Osu>,
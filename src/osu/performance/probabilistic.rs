@@ -0,0 +1,469 @@
+use std::{cmp, f64::consts::TAU};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{mode::ConvertError, mods::GameMods},
+    osu::{
+        attributes::OsuDifficultyAttributes,
+        difficulty::{skills::OsuSkills, start_times, DifficultyValues},
+        object::{OsuObject, OsuObjectKind},
+        score_state::{OsuScoreOrigin, OsuScoreState},
+    },
+    util::random::Random,
+    Beatmap, Difficulty,
+};
+
+use super::OsuPerformance;
+
+/// Model of a player's skill used by [`OsuProbabilisticPerformance`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[must_use]
+pub struct OsuPlayerModel {
+    /// Standard deviation of the player's hit error in milliseconds.
+    pub hit_error_sd: f64,
+    /// Probability to miss an object with the lowest strain of the map.
+    pub min_miss_probability: f64,
+    /// Probability to miss an object with the highest strain of the map.
+    pub max_miss_probability: f64,
+}
+
+impl OsuPlayerModel {
+    /// Create a new player model with the given hit error standard deviation
+    /// in milliseconds and no misses.
+    pub const fn new(hit_error_sd: f64) -> Self {
+        Self {
+            hit_error_sd,
+            min_miss_probability: 0.0,
+            max_miss_probability: 0.0,
+        }
+    }
+
+    /// Specify the miss probabilities between `0.0` and `1.0` for the
+    /// map's lowest and highest strain levels.
+    ///
+    /// Objects in between are interpolated linearly w.r.t. their strain.
+    pub const fn miss_probability(mut self, min: f64, max: f64) -> Self {
+        self.min_miss_probability = min.clamp(0.0, 1.0);
+        self.max_miss_probability = max.clamp(0.0, 1.0);
+
+        self
+    }
+
+    fn miss_probability_at(&self, strain_level: f64) -> f64 {
+        self.min_miss_probability
+            + (self.max_miss_probability - self.min_miss_probability) * strain_level
+    }
+}
+
+impl Default for OsuPlayerModel {
+    fn default() -> Self {
+        Self::new(OsuProbabilisticPerformance::DEFAULT_HIT_ERROR_SD)
+    }
+}
+
+/// Estimate the distribution of a player's performance on an osu!standard
+/// map through Monte Carlo sampling.
+///
+/// Every trial simulates a play by sampling a hit error and a potential miss
+/// for each hit object based on the given [`OsuPlayerModel`].
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::Beatmap;
+/// use akatsuki_pp::osu::{OsuPlayerModel, OsuProbabilisticPerformance};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let player = OsuPlayerModel::new(15.0).miss_probability(0.0005, 0.01);
+///
+/// let distribution = OsuProbabilisticPerformance::new(&map)
+///     .mods(8) // HD
+///     .player(player)
+///     .trials(200)
+///     .calculate()
+///     .unwrap();
+///
+/// println!(
+///     "Expected pp: {:.2} (median {:.2})",
+///     distribution.pp,
+///     distribution.percentile(50.0),
+/// );
+/// ```
+#[derive(Clone)]
+#[must_use]
+pub struct OsuProbabilisticPerformance<'map> {
+    map: &'map Beatmap,
+    difficulty: Difficulty,
+    player: OsuPlayerModel,
+    trials: usize,
+    seed: i32,
+}
+
+impl<'map> OsuProbabilisticPerformance<'map> {
+    const DEFAULT_HIT_ERROR_SD: f64 = 20.0;
+    const DEFAULT_TRIALS: usize = 1000;
+    const DEFAULT_SEED: i32 = 1337;
+
+    /// Create a new probabilistic performance calculator for the given map.
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            difficulty: Difficulty::new(),
+            player: OsuPlayerModel::default(),
+            trials: Self::DEFAULT_TRIALS,
+            seed: Self::DEFAULT_SEED,
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
+    ///
+    /// See <https://github.com/ppy/osu-api/wiki#mods>
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.difficulty = self.difficulty.mods(mods);
+
+        self
+    }
+
+    /// Whether the calculated attributes belong to an osu!lazer or
+    /// osu!stable score.
+    ///
+    /// Defaults to `true`.
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.difficulty = self.difficulty.lazer(lazer);
+
+        self
+    }
+
+    /// Use the specified settings of the given [`Difficulty`].
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+
+        self
+    }
+
+    /// Specify the [`OsuPlayerModel`] to simulate plays with.
+    pub const fn player(mut self, player: OsuPlayerModel) -> Self {
+        self.player = player;
+
+        self
+    }
+
+    /// Amount of simulated plays.
+    ///
+    /// Defaults to `1000`.
+    pub fn trials(mut self, trials: usize) -> Self {
+        self.trials = cmp::max(trials, 1);
+
+        self
+    }
+
+    /// Seed for the random number generator.
+    ///
+    /// The same seed and settings always produce the same distribution.
+    pub const fn seed(mut self, seed: i32) -> Self {
+        self.seed = seed;
+
+        self
+    }
+
+    /// Simulate the plays and aggregate their results.
+    pub fn calculate(&self) -> Result<OsuPerformanceDistribution, ConvertError> {
+        let map = self
+            .map
+            .convert_ref(GameMode::Osu, self.difficulty.get_mods())?;
+
+        let (attrs, objects) = simulated_objects(&self.difficulty, &map);
+
+        let mods = self.difficulty.get_mods();
+        let lazer = self.difficulty.get_lazer();
+        let using_classic_slider_acc = mods.no_slider_head_acc(lazer);
+
+        let origin = match (lazer, using_classic_slider_acc) {
+            (false, _) => OsuScoreOrigin::Stable,
+            (true, false) => OsuScoreOrigin::WithSliderAcc {
+                max_large_ticks: attrs.n_large_ticks,
+                max_slider_ends: attrs.n_sliders,
+            },
+            (true, true) => OsuScoreOrigin::WithoutSliderAcc {
+                max_large_ticks: attrs.n_sliders + attrs.n_large_ticks,
+                max_slider_ends: attrs.n_sliders,
+            },
+        };
+
+        // Hit windows in real time, taking clock rate into account
+        let great = 80.0 - 6.0 * attrs.od;
+        let ok = 140.0 - 8.0 * attrs.od;
+        let meh = 200.0 - 10.0 * attrs.od;
+
+        let mut rng = Random::new(self.seed);
+        let mut samples = Vec::with_capacity(self.trials);
+        let mut acc_sum = 0.0;
+        let mut misses_sum = 0;
+
+        for _ in 0..self.trials {
+            let mut state = OsuScoreState::new();
+            let mut combo = 0;
+
+            for obj in objects.iter() {
+                if obj.is_spinner {
+                    state.n300 += 1;
+                    combo += obj.combo;
+                    state.max_combo = cmp::max(state.max_combo, combo);
+
+                    continue;
+                }
+
+                let missed = rng.next_double() < self.player.miss_probability_at(obj.strain_level);
+                let hit_error = (next_gaussian(&mut rng) * self.player.hit_error_sd).abs();
+
+                if missed || hit_error > meh {
+                    state.misses += 1;
+                    combo = 0;
+
+                    continue;
+                }
+
+                if hit_error <= great {
+                    state.n300 += 1;
+                } else if hit_error <= ok {
+                    state.n100 += 1;
+                } else {
+                    state.n50 += 1;
+                }
+
+                if obj.is_slider {
+                    state.large_tick_hits += obj.large_ticks + u32::from(using_classic_slider_acc);
+                    state.slider_end_hits += 1;
+                }
+
+                combo += obj.combo;
+                state.max_combo = cmp::max(state.max_combo, combo);
+            }
+
+            acc_sum += state.accuracy(origin);
+            misses_sum += state.misses;
+
+            let pp = OsuPerformance::from(attrs.clone())
+                .difficulty(self.difficulty.clone())
+                .state(state)
+                .calculate()?
+                .pp;
+
+            samples.push(pp);
+        }
+
+        samples.sort_by(f64::total_cmp);
+
+        let trials = samples.len() as f64;
+        let pp = samples.iter().sum::<f64>() / trials;
+
+        let variance = samples.iter().map(|x| (x - pp) * (x - pp)).sum::<f64>() / trials;
+
+        Ok(OsuPerformanceDistribution {
+            difficulty: attrs,
+            accuracy: 100.0 * acc_sum / trials,
+            misses: f64::from(misses_sum) / trials,
+            pp,
+            pp_std_dev: variance.sqrt(),
+            samples,
+        })
+    }
+}
+
+/// The result of an [`OsuProbabilisticPerformance`] calculation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuPerformanceDistribution {
+    /// The difficulty attributes that were used for the simulation.
+    pub difficulty: OsuDifficultyAttributes,
+    /// The expected accuracy between `0.0` and `100.0`.
+    pub accuracy: f64,
+    /// The expected amount of misses.
+    pub misses: f64,
+    /// The expected performance points.
+    pub pp: f64,
+    /// The standard deviation of the performance points.
+    pub pp_std_dev: f64,
+    /// The performance points of each trial, sorted in ascending order.
+    pub samples: Vec<f64>,
+}
+
+impl OsuPerformanceDistribution {
+    /// Return the performance points at the given percentile between `0.0`
+    /// and `100.0`.
+    pub fn percentile(&self, percentile: f64) -> f64 {
+        let Some(last) = self.samples.len().checked_sub(1) else {
+            return 0.0;
+        };
+
+        let idx = (percentile.clamp(0.0, 100.0) / 100.0 * last as f64).round() as usize;
+
+        self.samples[idx]
+    }
+}
+
+struct SimulatedObject {
+    combo: u32,
+    large_ticks: u32,
+    strain_level: f64,
+    is_slider: bool,
+    is_spinner: bool,
+}
+
+fn simulated_objects(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> (OsuDifficultyAttributes, Vec<SimulatedObject>) {
+    let difficulty = &*difficulty.resolve_passed_time(|| start_times(map));
+
+    let (
+        DifficultyValues {
+            skills:
+                OsuSkills {
+                    aim,
+                    aim_no_sliders,
+                    speed,
+                    flashlight,
+                },
+            mut attrs,
+        },
+        osu_objects,
+    ) = DifficultyValues::calculate_with_objects(difficulty, map);
+
+    let aim_difficulty_value = aim.difficulty_value();
+    let aim_no_sliders_difficulty_value = aim_no_sliders.difficulty_value();
+    let speed_relevant_note_count = speed.relevant_note_count();
+    let speed_difficulty_value = speed.difficulty_value();
+    let flashlight_difficulty_value = flashlight.difficulty_value();
+
+    DifficultyValues::eval(
+        &mut attrs,
+        difficulty.get_mods(),
+        &aim_difficulty_value,
+        &aim_no_sliders_difficulty_value,
+        &speed_difficulty_value,
+        speed_relevant_note_count,
+        flashlight_difficulty_value,
+    );
+
+    let aim_strains = aim_difficulty_value.object_strains();
+    let speed_strains = speed_difficulty_value.object_strains();
+
+    let max_of = |strains: &[f64]| strains.iter().copied().fold(0.0, f64::max);
+    let aim_max = max_of(aim_strains);
+    let speed_max = max_of(speed_strains);
+
+    let relative = |strains: &[f64], max: f64, i: usize| {
+        if max > 0.0 {
+            strains.get(i).map_or(0.0, |strain| strain / max)
+        } else {
+            0.0
+        }
+    };
+
+    let take = cmp::min(difficulty.get_passed_objects(), osu_objects.len());
+
+    let objects = osu_objects[..take]
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            // The first hit object has no difficulty object
+            let strain_level = i.checked_sub(1).map_or(0.0, |i| {
+                relative(aim_strains, aim_max, i).max(relative(speed_strains, speed_max, i))
+            });
+
+            SimulatedObject::new(h, strain_level)
+        })
+        .collect();
+
+    (attrs, objects)
+}
+
+impl SimulatedObject {
+    fn new(h: &OsuObject, strain_level: f64) -> Self {
+        match h.kind {
+            OsuObjectKind::Circle => Self {
+                combo: 1,
+                large_ticks: 0,
+                strain_level,
+                is_slider: false,
+                is_spinner: false,
+            },
+            OsuObjectKind::Slider(ref slider) => Self {
+                combo: 1 + slider.nested_objects.len() as u32,
+                large_ticks: slider.large_tick_count() as u32,
+                strain_level,
+                is_slider: true,
+                is_spinner: false,
+            },
+            OsuObjectKind::Spinner(_) => Self {
+                combo: 1,
+                large_ticks: 0,
+                strain_level,
+                is_slider: false,
+                is_spinner: true,
+            },
+        }
+    }
+}
+
+/// Sample from the standard normal distribution via Box-Muller transform.
+fn next_gaussian(rng: &mut Random) -> f64 {
+    let u1 = 1.0 - rng.next_double();
+    let u2 = rng.next_double();
+
+    (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perfect_player_matches_ss() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let distribution = OsuProbabilisticPerformance::new(&map)
+            .player(OsuPlayerModel::new(0.0))
+            .trials(5)
+            .calculate()
+            .unwrap();
+
+        let ss = OsuPerformance::from(&map).calculate().unwrap();
+
+        assert!((distribution.accuracy - 100.0).abs() < f64::EPSILON);
+        assert!(distribution.misses.abs() < f64::EPSILON);
+        assert!(
+            (distribution.pp - ss.pp).abs() < 1e-6,
+            "{} != {}",
+            distribution.pp,
+            ss.pp
+        );
+    }
+
+    #[test]
+    fn same_seed_same_distribution() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let player = OsuPlayerModel::new(25.0).miss_probability(0.001, 0.02);
+        let calc = OsuProbabilisticPerformance::new(&map)
+            .player(player)
+            .trials(20)
+            .seed(42);
+
+        let a = calc.calculate().unwrap();
+        let b = calc.calculate().unwrap();
+
+        assert_eq!(a, b);
+        assert!(a.misses > 0.0);
+        assert!(a.percentile(0.0) <= a.percentile(100.0));
+    }
+}
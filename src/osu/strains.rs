@@ -1,6 +1,10 @@
 use rosu_map::section::general::GameMode;
 
-use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+use crate::{
+    any::{sections, HardSection, StrainTimeline},
+    model::mode::ConvertError,
+    Beatmap, Difficulty,
+};
 
 use super::difficulty::{skills::OsuSkills, DifficultyValues};
 
@@ -22,6 +26,20 @@ pub struct OsuStrains {
 impl OsuStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 400.0;
+
+    /// The timing of the strain sections of the given map.
+    pub fn timeline(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+    ) -> Result<StrainTimeline, ConvertError> {
+        let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+        Ok(StrainTimeline::new(
+            map.hit_objects.get(1).map(|h| h.start_time),
+            Self::SECTION_LEN,
+            difficulty.get_clock_rate(),
+        ))
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<OsuStrains, ConvertError> {
@@ -45,3 +63,215 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<OsuStrains, Con
         flashlight: flashlight.get_curr_strain_peaks().into_vec(),
     })
 }
+
+/// The strain values of a single hit object of an osu! map.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OsuObjectStrain {
+    /// Index of the hit object in the map.
+    pub idx: usize,
+    /// Start time of the hit object in ms, unaffected by the clock rate.
+    pub start_time: f64,
+    /// Strain value of the aim skill.
+    pub aim: f64,
+    /// Strain value of the aim skill without sliders.
+    pub aim_no_sliders: f64,
+    /// Strain value of the speed skill.
+    pub speed: f64,
+    /// Strain value of the flashlight skill.
+    pub flashlight: f64,
+}
+
+pub fn object_strains(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<Vec<OsuObjectStrain>, ConvertError> {
+    let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+    let DifficultyValues {
+        skills:
+            OsuSkills {
+                aim,
+                aim_no_sliders,
+                speed,
+                flashlight,
+            },
+        attrs: _,
+    } = DifficultyValues::calculate_object_strains(difficulty, &map);
+
+    let aim = aim.into_object_strains();
+    let aim_no_sliders = aim_no_sliders.into_object_strains();
+    let speed = speed.into_object_strains();
+    let flashlight = flashlight.into_object_strains();
+
+    // The first hit object has no difficulty object
+    let object_strains = map
+        .hit_objects
+        .iter()
+        .enumerate()
+        .skip(1)
+        .zip(aim.into_iter().zip(aim_no_sliders))
+        .zip(speed.into_iter().zip(flashlight))
+        .map(
+            |(((idx, h), (aim, aim_no_sliders)), (speed, flashlight))| OsuObjectStrain {
+                idx,
+                start_time: h.start_time,
+                aim,
+                aim_no_sliders,
+                speed,
+                flashlight,
+            },
+        )
+        .collect();
+
+    Ok(object_strains)
+}
+
+/// The hardest sections of a osu! map for each skill, sorted by their
+/// peak strain in descending order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuHardestSections {
+    /// Hardest sections of the aim skill.
+    pub aim: Vec<HardSection>,
+    /// Hardest sections of the aim skill without sliders.
+    pub aim_no_sliders: Vec<HardSection>,
+    /// Hardest sections of the speed skill.
+    pub speed: Vec<HardSection>,
+    /// Hardest sections of the flashlight skill.
+    pub flashlight: Vec<HardSection>,
+}
+
+pub fn hardest_sections(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    count: usize,
+) -> Result<OsuHardestSections, ConvertError> {
+    let object_strains = object_strains(difficulty, map)?;
+
+    let timeline = StrainTimeline::new(
+        object_strains.first().map(|s| s.start_time),
+        OsuStrains::SECTION_LEN,
+        difficulty.get_clock_rate(),
+    );
+
+    Ok(OsuHardestSections {
+        aim: sections::hardest_sections(
+            &timeline,
+            object_strains.iter().map(|s| (s.idx, s.start_time, s.aim)),
+            count,
+        ),
+        aim_no_sliders: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.aim_no_sliders)),
+            count,
+        ),
+        speed: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.speed)),
+            count,
+        ),
+        flashlight: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.flashlight)),
+            count,
+        ),
+    })
+}
+
+impl OsuObjectStrain {
+    /// Perform a difficulty calculation and return the strain values of each
+    /// hit object.
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Vec<Self>, ConvertError> {
+        object_strains(difficulty, map)
+    }
+}
+
+impl OsuHardestSections {
+    /// Perform a difficulty calculation and return the `count` hardest
+    /// sections for each skill.
+    pub fn calculate(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        count: usize,
+    ) -> Result<Self, ConvertError> {
+        hardest_sections(difficulty, map, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_strains_match_peaks() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new();
+
+        let peaks = strains(&difficulty, &map).unwrap();
+        let object_strains = object_strains(&difficulty, &map).unwrap();
+
+        assert_eq!(object_strains.len(), map.hit_objects.len() - 1);
+
+        for (strain, h) in object_strains.iter().zip(map.hit_objects.iter().skip(1)) {
+            assert!((strain.start_time - h.start_time).abs() < f64::EPSILON);
+        }
+
+        let max_peak = peaks.aim.iter().copied().fold(0.0, f64::max);
+        let max_strain = object_strains.iter().map(|s| s.aim).fold(0.0, f64::max);
+
+        assert!((max_peak - max_strain).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn timeline_matches_objects() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new().mods(64); // DT
+
+        let peaks = strains(&difficulty, &map).unwrap();
+        let object_strains = object_strains(&difficulty, &map).unwrap();
+        let timeline = OsuStrains::timeline(&difficulty, &map).unwrap();
+
+        assert!((timeline.clock_rate - 1.5).abs() < f64::EPSILON);
+
+        for strain in object_strains.iter() {
+            let idx = timeline.section_at(strain.start_time).unwrap();
+            let section = timeline.section(idx);
+
+            assert!(section.map_start < strain.start_time);
+            assert!(strain.start_time <= section.map_end + 1e-9);
+            assert!(peaks.aim[idx] >= strain.aim);
+        }
+
+        let last = timeline.section(peaks.aim.len() - 1);
+        let last_object = object_strains.last().unwrap();
+        assert!(last.map_start < last_object.start_time);
+    }
+
+    #[test]
+    fn resampled_strains_line_up() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let resample = |difficulty: Difficulty| {
+            let strains = strains(&difficulty, &map).unwrap();
+            let timeline = OsuStrains::timeline(&difficulty, &map).unwrap();
+
+            timeline.resample(&strains.aim, 1000.0)
+        };
+
+        let nomod = resample(Difficulty::new());
+        let dt = resample(Difficulty::new().mods(64));
+
+        let len = (map.hit_objects.last().unwrap().start_time / 1000.0).ceil() as usize;
+        assert!(nomod.len().abs_diff(len) <= 1);
+        assert!(dt.len().abs_diff(len) <= 1);
+
+        // Skipping ahead to the first object results in the same leading zeros
+        let leading_zeros = |strains: &[f64]| strains.iter().take_while(|&&s| s == 0.0).count();
+        assert_eq!(leading_zeros(&nomod), leading_zeros(&dt));
+    }
+}
@@ -4,7 +4,7 @@ use rosu_map::{section::general::GameMode, util::Pos};
 
 use crate::{
     model::{
-        beatmap::Beatmap,
+        beatmap::{Beatmap, BeatmapStatistics},
         control_point::{DifficultyPoint, TimingPoint},
         hit_object::{HitObject, HitObjectKind, HoldNote, Slider, Spinner},
     },
@@ -91,6 +91,40 @@ pub fn convert(map: &mut Beatmap) {
     map.is_convert = true;
 }
 
+pub fn statistics(map: &Beatmap, stats: &mut BeatmapStatistics) {
+    for h in map.hit_objects.iter() {
+        let duration = match h.kind {
+            HitObjectKind::Circle => {
+                stats.n_circles += 1;
+                stats.max_combo += 1;
+
+                0.0
+            }
+            HitObjectKind::Slider(ref slider) => {
+                stats.n_sliders += 1;
+
+                drum_roll_duration(map, h.start_time, slider)
+            }
+            HitObjectKind::Spinner(Spinner { duration })
+            | HitObjectKind::Hold(HoldNote { duration }) => {
+                stats.n_spinners += 1;
+
+                duration
+            }
+        };
+
+        stats.last_object_time = stats.last_object_time.max(h.start_time + duration);
+    }
+}
+
+/// The duration of a slider as drum roll.
+pub fn drum_roll_duration(map: &Beatmap, start_time: f64, slider: &Slider) -> f64 {
+    let mut params = SliderParams::new(start_time, slider);
+    should_convert_slider_to_taiko_hits(map, &mut params);
+
+    f64::from(params.duration)
+}
+
 fn should_convert_slider_to_taiko_hits(map: &Beatmap, params: &mut SliderParams<'_>) -> bool {
     let SliderParams {
         slider,
@@ -7,7 +7,7 @@ use crate::{
 
 use super::alternating_mono_pattern::AlternatingMonoPattern;
 
-const MAX_REPETITION_INTERVAL: usize = 16;
+pub(crate) const MAX_REPETITION_INTERVAL: usize = 16;
 
 #[derive(Debug)]
 pub struct RepeatingHitPatterns {
@@ -5,7 +5,7 @@ use rosu_map::section::general::GameMode;
 use crate::{
     any::difficulty::skills::Skill,
     model::{beatmap::HitWindows, hit_object::HitObject, mode::ConvertError},
-    util::sync::RefCount,
+    util::{checkpoints::Checkpoints, sync::RefCount},
     Beatmap, Difficulty,
 };
 
@@ -54,8 +54,16 @@ pub struct TaikoGradualDifficulty {
     diff_objects: TaikoDifficultyObjects,
     diff_objects_iter: Iter<'static, RefCount<TaikoDifficultyObject>>,
     skills: TaikoSkills,
-    total_hits: usize,
+    // Start times of all circles
+    start_times: Box<[f64]>,
     first_combos: FirstTwoCombos,
+    checkpoints: Checkpoints<Snapshot>,
+}
+
+struct Snapshot {
+    attrs: TaikoDifficultyAttributes,
+    skills: TaikoSkills,
+    diff_objects_iter: Iter<'static, RefCount<TaikoDifficultyObject>>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -68,10 +76,20 @@ enum FirstTwoCombos {
 
 impl TaikoGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!taiko maps.
+    ///
+    /// Objects past [`Difficulty::passed_objects`] or [`Difficulty::passed_time`]
+    /// are not processed.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
         let map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
 
-        let take = difficulty.get_passed_objects();
+        let mut start_times: Vec<f64> = super::start_times(&map).collect();
+
+        let take = difficulty
+            .resolve_passed_time(|| start_times.iter().copied())
+            .get_passed_objects();
+
+        start_times.truncate(take);
+
         let clock_rate = difficulty.get_clock_rate();
 
         let first_combos = match (
@@ -110,10 +128,14 @@ impl TaikoGradualDifficulty {
             ..Default::default()
         };
 
-        let total_hits = map.hit_objects.iter().filter(|h| h.is_circle()).count();
-
         let diff_objects_iter = extend_lifetime(diff_objects.iter());
 
+        let checkpoints = Checkpoints::new(Snapshot {
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+            diff_objects_iter: diff_objects_iter.clone(),
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
@@ -121,74 +143,99 @@ impl TaikoGradualDifficulty {
             diff_objects_iter,
             skills,
             attrs,
-            total_hits,
+            start_times: start_times.into_boxed_slice(),
             first_combos,
+            checkpoints,
         })
     }
-}
-
-fn extend_lifetime(
-    iter: Iter<'_, RefCount<TaikoDifficultyObject>>,
-) -> Iter<'static, RefCount<TaikoDifficultyObject>> {
-    // SAFETY: The underlying data will never be moved.
-    unsafe { mem::transmute(iter) }
-}
-
-impl Iterator for TaikoGradualDifficulty {
-    type Item = TaikoDifficultyAttributes;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // The first difficulty object belongs to the third note since each
-        // difficulty object requires the current, the last, and the second to
-        // last note. Hence, if we're still on the first or second object, we
-        // don't have a difficulty object yet and just skip processing.
-        if self.idx >= 2 {
-            loop {
-                let curr = self.diff_objects_iter.next()?;
-                let borrowed = curr.get();
 
-                Skill::new(&mut self.skills.rhythm, &self.diff_objects).process(&borrowed);
-                Skill::new(&mut self.skills.color, &self.diff_objects).process(&borrowed);
-                Skill::new(&mut self.skills.stamina, &self.diff_objects).process(&borrowed);
-                Skill::new(&mut self.skills.single_color_stamina, &self.diff_objects)
-                    .process(&borrowed);
+    /// Store a snapshot of the current state every `interval` hit objects.
+    ///
+    /// Snapshots allow [`seek`] to go backwards without processing all
+    /// previous hit objects again. Smaller intervals make seeking faster but
+    /// require more memory. An interval of `0` disables snapshots.
+    ///
+    /// [`seek`]: TaikoGradualDifficulty::seek
+    #[must_use]
+    pub const fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
 
-                if borrowed.base_hit_type.is_hit() {
-                    self.attrs.max_combo += 1;
+    /// Move to the state after processing the first `idx` hit objects and
+    /// return the resulting attributes.
+    ///
+    /// Seeking backwards restores the latest snapshot before `idx`, see
+    /// [`checkpoint_interval`]. Values of `idx` larger than the amount of
+    /// hit objects will process all hit objects.
+    ///
+    /// Returns `None` if `idx` is `0` or the map has no hit objects.
+    ///
+    /// [`checkpoint_interval`]: TaikoGradualDifficulty::checkpoint_interval
+    pub fn seek(&mut self, idx: usize) -> Option<TaikoDifficultyAttributes> {
+        let Some(n) = idx.checked_sub(1) else {
+            self.restore(0);
 
-                    break;
-                }
-            }
-        } else if self.diff_objects.is_empty() {
             return None;
-        } else {
-            match self.first_combos {
-                FirstTwoCombos::OnlyFirst => self.attrs.max_combo = 1,
-                FirstTwoCombos::OnlySecond if self.idx == 1 => self.attrs.max_combo = 1,
-                FirstTwoCombos::Both if self.idx == 0 => self.attrs.max_combo = 1,
-                FirstTwoCombos::Both if self.idx == 1 => self.attrs.max_combo = 2,
-                _ => {}
-            }
-        }
+        };
 
-        self.idx += 1;
+        if n < self.idx {
+            self.restore(n);
+        }
 
-        let mut attrs = self.attrs.clone();
+        self.nth(n - self.idx)
+    }
 
-        DifficultyValues::eval(&mut attrs, self.skills.clone());
+    /// Process all hit objects that started within the given amount of
+    /// milliseconds and return the resulting attributes.
+    ///
+    /// The time is measured as it elapses during gameplay, i.e. the clock rate
+    /// is taken into account. See [`Difficulty::passed_time`].
+    ///
+    /// Returns `None` if no further hit object started within that time.
+    pub fn passed_time(&mut self, passed_time: f64) -> Option<TaikoDifficultyAttributes> {
+        self.remaining_until(passed_time)
+            .checked_sub(1)
+            .and_then(|n| self.nth(n))
+    }
 
-        Some(attrs)
+    /// Amount of hit objects that remain to be processed until the given
+    /// amount of milliseconds passed.
+    pub(crate) fn remaining_until(&self, passed_time: f64) -> usize {
+        self.difficulty
+            .count_passed(&self.start_times, passed_time)
+            .saturating_sub(self.idx)
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.len();
+    fn restore(&mut self, idx: usize) {
+        let (idx, snapshot) = self.checkpoints.latest_until(idx);
 
-        (len, Some(len))
+        self.idx = idx;
+        self.attrs.clone_from(&snapshot.attrs);
+        self.skills = snapshot.skills.clone();
+        self.diff_objects_iter = snapshot.diff_objects_iter.clone();
     }
 
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
+    fn record_checkpoint(&mut self) {
+        let Self {
+            idx,
+            attrs,
+            skills,
+            diff_objects_iter,
+            checkpoints,
+            ..
+        } = self;
+
+        checkpoints.record(*idx, || Snapshot {
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+            diff_objects_iter: diff_objects_iter.clone(),
+        });
+    }
 
+    /// Process the next `take` hit objects without evaluating them.
+    fn advance(&mut self, mut take: usize) {
         // The first two notes have no difficulty object but might add to combo
         match (take, self.idx) {
             (_, 2..) | (0, _) => {}
@@ -235,7 +282,9 @@ impl Iterator for TaikoGradualDifficulty {
 
         for _ in 0..take {
             loop {
-                let curr = self.diff_objects_iter.next()?;
+                let Some(curr) = self.diff_objects_iter.next() else {
+                    return;
+                };
                 let borrowed = curr.get();
                 rhythm.process(&borrowed);
                 color.process(&borrowed);
@@ -250,6 +299,80 @@ impl Iterator for TaikoGradualDifficulty {
                 }
             }
         }
+    }
+}
+
+fn extend_lifetime(
+    iter: Iter<'_, RefCount<TaikoDifficultyObject>>,
+) -> Iter<'static, RefCount<TaikoDifficultyObject>> {
+    // SAFETY: The underlying data will never be moved.
+    unsafe { mem::transmute(iter) }
+}
+
+impl Iterator for TaikoGradualDifficulty {
+    type Item = TaikoDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The first difficulty object belongs to the third note since each
+        // difficulty object requires the current, the last, and the second to
+        // last note. Hence, if we're still on the first or second object, we
+        // don't have a difficulty object yet and just skip processing.
+        if self.idx >= self.start_times.len() {
+            return None;
+        } else if self.idx >= 2 {
+            loop {
+                let curr = self.diff_objects_iter.next()?;
+                let borrowed = curr.get();
+
+                Skill::new(&mut self.skills.rhythm, &self.diff_objects).process(&borrowed);
+                Skill::new(&mut self.skills.color, &self.diff_objects).process(&borrowed);
+                Skill::new(&mut self.skills.stamina, &self.diff_objects).process(&borrowed);
+                Skill::new(&mut self.skills.single_color_stamina, &self.diff_objects)
+                    .process(&borrowed);
+
+                if borrowed.base_hit_type.is_hit() {
+                    self.attrs.max_combo += 1;
+
+                    break;
+                }
+            }
+        } else if self.diff_objects.is_empty() {
+            return None;
+        } else {
+            match self.first_combos {
+                FirstTwoCombos::OnlyFirst => self.attrs.max_combo = 1,
+                FirstTwoCombos::OnlySecond if self.idx == 1 => self.attrs.max_combo = 1,
+                FirstTwoCombos::Both if self.idx == 0 => self.attrs.max_combo = 1,
+                FirstTwoCombos::Both if self.idx == 1 => self.attrs.max_combo = 2,
+                _ => {}
+            }
+        }
+
+        self.idx += 1;
+        self.record_checkpoint();
+
+        let mut attrs = self.attrs.clone();
+
+        DifficultyValues::eval(&mut attrs, self.skills.clone());
+
+        Some(attrs)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let mut take = cmp::min(n, self.len().saturating_sub(1));
+
+        while take > 0 {
+            let step = cmp::min(take, self.checkpoints.steps_until_due(self.idx));
+            self.advance(step);
+            self.record_checkpoint();
+            take -= step;
+        }
 
         self.next()
     }
@@ -257,7 +380,7 @@ impl Iterator for TaikoGradualDifficulty {
 
 impl ExactSizeIterator for TaikoGradualDifficulty {
     fn len(&self) -> usize {
-        self.total_hits - self.idx
+        self.start_times.len() - self.idx
     }
 }
 
@@ -315,4 +438,55 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn stops_at_difficulty_passed_time() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64).passed_time(12345.0);
+        let gradual = TaikoGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        let expected = difficulty.calculate_for_mode::<Taiko>(&map).unwrap();
+
+        assert_eq!(gradual.last(), Some(expected));
+    }
+
+    #[test]
+    fn passed_time_caps_remaining_objects() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new().passed_time(12345.0);
+        let gradual = TaikoGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.remaining_until(f64::MAX), gradual.len());
+
+        // Nothing passed before the first object
+        let difficulty = Difficulty::new().passed_time(-1.0);
+        let mut gradual = TaikoGradualDifficulty::new(difficulty, &map).unwrap();
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.next(), None);
+    }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new();
+        let mut gradual = TaikoGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .checkpoint_interval(50);
+
+        let len = gradual.len();
+
+        for idx in [len / 2, 10, len, 1, 2, 137, 0, 99, 100] {
+            let expected = idx.checked_sub(1).and_then(|n| {
+                TaikoGradualDifficulty::new(difficulty.clone(), &map)
+                    .unwrap()
+                    .nth(n)
+            });
+
+            assert_eq!(gradual.seek(idx), expected, "idx={idx}");
+        }
+    }
 }
@@ -22,6 +22,7 @@ use super::attributes::TaikoDifficultyAttributes;
 mod color;
 pub mod gradual;
 mod object;
+pub mod pattern;
 mod rhythm;
 mod skills;
 
@@ -116,6 +117,15 @@ fn rescale(stars: f64) -> f64 {
     }
 }
 
+/// Start times of all objects that count towards the amount of passed objects.
+pub(crate) fn start_times(converted: &Beatmap) -> impl Iterator<Item = f64> + '_ {
+    converted
+        .hit_objects
+        .iter()
+        .filter(|h| h.is_circle())
+        .map(|h| h.start_time)
+}
+
 pub struct DifficultyValues {
     pub skills: TaikoSkills,
     pub max_combo: u32,
@@ -123,6 +133,17 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &Difficulty, converted: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, converted, false)
+    }
+
+    /// Same as [`DifficultyValues::calculate`] but the skills also store the
+    /// strain value of each object.
+    pub fn calculate_object_strains(difficulty: &Difficulty, converted: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, converted, true)
+    }
+
+    fn calculate_with(difficulty: &Difficulty, converted: &Beatmap, object_strains: bool) -> Self {
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(converted));
         let take = difficulty.get_passed_objects();
         let clock_rate = difficulty.get_clock_rate();
 
@@ -142,6 +163,10 @@ impl DifficultyValues {
 
         let mut skills = TaikoSkills::new();
 
+        if object_strains {
+            skills.enable_object_strains();
+        }
+
         {
             let mut rhythm = Skill::new(&mut skills.rhythm, &diff_objects);
             let mut color = Skill::new(&mut skills.color, &diff_objects);
@@ -0,0 +1,424 @@
+use std::ops::Range;
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::difficulty::skills::Skill,
+    model::mode::ConvertError,
+    taiko::object::HitType,
+    util::{
+        runs,
+        sync::{RefCount, Weak},
+    },
+    Beatmap, Difficulty,
+};
+
+use super::{
+    color::repeating_hit_patterns::{RepeatingHitPatterns, MAX_REPETITION_INTERVAL},
+    object::TaikoDifficultyObjects,
+    skills::stamina::Stamina,
+    start_times, DifficultyValues,
+};
+
+/// Stamina strains of at least this fraction of the map's highest stamina
+/// strain are considered stamina-heavy.
+const STAMINA_HEAVY_RATIO: f64 = 0.7;
+/// Minimum amount of objects in a stamina-heavy segment.
+const STAMINA_HEAVY_MIN_OBJECTS: usize = 8;
+
+/// The color of an osu!taiko note.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TaikoColor {
+    /// A center note, i.e. don.
+    Center,
+    /// A rim note, i.e. kat.
+    Rim,
+}
+
+/// A range of hit objects in a converted osu!taiko map.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TaikoObjectRange {
+    /// Index of the first hit object.
+    pub first_object: usize,
+    /// Index of the last hit object.
+    pub last_object: usize,
+    /// Start time of the first hit object in map time in ms.
+    pub start_time: f64,
+    /// Start time of the last hit object in map time in ms.
+    pub end_time: f64,
+}
+
+impl TaikoObjectRange {
+    const fn to(self, other: Self) -> Self {
+        Self {
+            first_object: self.first_object,
+            last_object: other.last_object,
+            start_time: self.start_time,
+            end_time: other.end_time,
+        }
+    }
+}
+
+/// Consecutive notes of the same color.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoMonoStreak {
+    /// The hit objects of the streak.
+    pub range: TaikoObjectRange,
+    /// The color of all notes of the streak.
+    pub color: TaikoColor,
+    /// The amount of notes in the streak.
+    pub n_notes: usize,
+}
+
+/// Consecutive [`TaikoMonoStreak`]s of alternating colors whose lengths are
+/// all the same, e.g. "kkdd kkdd" or "kd kd".
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoAlternatingMonoPattern {
+    /// The hit objects of the pattern.
+    pub range: TaikoObjectRange,
+    /// Indices into [`TaikoPatterns::mono_streaks`].
+    pub mono_streaks: Range<usize>,
+}
+
+/// Consecutive [`TaikoAlternatingMonoPattern`]s that repeat a previous group
+/// of patterns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoRepeatingHitPattern {
+    /// The hit objects of the pattern.
+    pub range: TaikoObjectRange,
+    /// Indices into [`TaikoPatterns::alternating_mono_patterns`].
+    pub alternating_mono_patterns: Range<usize>,
+    /// The amount of repeating hit patterns since the last time this pattern
+    /// occurred, or `None` if it did not occur recently.
+    pub repetition_interval: Option<usize>,
+}
+
+/// Consecutive hit objects with the same rhythm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoRhythmSegment {
+    /// The hit objects of the segment.
+    pub range: TaikoObjectRange,
+    /// Ratio between the time between objects of this segment and the
+    /// previous one.
+    pub ratio: f64,
+    /// The difficulty of the rhythm change into this segment.
+    pub difficulty: f64,
+}
+
+/// Consecutive hit objects with a particularly high stamina strain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoStaminaSegment {
+    /// The hit objects of the segment.
+    pub range: TaikoObjectRange,
+    /// The highest stamina strain within the segment.
+    pub peak: f64,
+}
+
+/// The color and rhythm encodings of an osu!taiko map as used by the
+/// difficulty calculation.
+///
+/// The first two hit objects have no difficulty object and are thus not
+/// included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoPatterns {
+    /// All mono streaks, sorted by time.
+    pub mono_streaks: Vec<TaikoMonoStreak>,
+    /// All alternating mono patterns, sorted by time.
+    pub alternating_mono_patterns: Vec<TaikoAlternatingMonoPattern>,
+    /// All repeating hit patterns, sorted by time.
+    pub repeating_hit_patterns: Vec<TaikoRepeatingHitPattern>,
+    /// All rhythm segments, sorted by time.
+    pub rhythm_segments: Vec<TaikoRhythmSegment>,
+    /// Segments whose stamina strain is close to the map's highest stamina
+    /// strain, sorted by time.
+    pub stamina_segments: Vec<TaikoStaminaSegment>,
+}
+
+impl TaikoPatterns {
+    /// Encode the colors and rhythm of a map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::taiko::TaikoPatterns;
+    ///
+    /// let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+    /// let patterns = TaikoPatterns::calculate(&Difficulty::new(), &map).unwrap();
+    ///
+    /// let longest = patterns.mono_streaks.iter().max_by_key(|streak| streak.n_notes).unwrap();
+    /// println!("Longest mono streak: {} {:?} notes", longest.n_notes, longest.color);
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(&map));
+
+        let mut n_diff_objects = 0;
+        let mut max_combo = 0;
+
+        let diff_objects = DifficultyValues::create_difficulty_objects(
+            &map,
+            difficulty.get_passed_objects() as u32,
+            difficulty.get_clock_rate(),
+            &mut max_combo,
+            &mut n_diff_objects,
+        );
+
+        // The first two hit objects have no difficulty object
+        n_diff_objects = n_diff_objects.saturating_sub(2);
+
+        let range = |first: usize, last: usize| TaikoObjectRange {
+            first_object: first + 2,
+            last_object: last + 2,
+            start_time: map.hit_objects[first + 2].start_time,
+            end_time: map.hit_objects[last + 2].start_time,
+        };
+
+        let (mono_streaks, alternating_mono_patterns, repeating_hit_patterns) =
+            color_patterns(&diff_objects, n_diff_objects, &range);
+
+        Ok(Self {
+            mono_streaks,
+            alternating_mono_patterns,
+            repeating_hit_patterns,
+            rhythm_segments: rhythm_segments(&diff_objects, n_diff_objects, &range),
+            stamina_segments: stamina_segments(&diff_objects, n_diff_objects, &range),
+        })
+    }
+}
+
+type ColorPatterns = (
+    Vec<TaikoMonoStreak>,
+    Vec<TaikoAlternatingMonoPattern>,
+    Vec<TaikoRepeatingHitPattern>,
+);
+
+fn color_patterns(
+    diff_objects: &TaikoDifficultyObjects,
+    n_diff_objects: usize,
+    range: &impl Fn(usize, usize) -> TaikoObjectRange,
+) -> ColorPatterns {
+    // Each repeating hit pattern knows its predecessor so we can collect
+    // all of them by starting at the last one.
+    let mut curr = diff_objects.iter().rev().find_map(|h| {
+        h.get()
+            .color
+            .repeating_hit_patterns
+            .as_ref()
+            .map(RefCount::clone)
+    });
+
+    let mut encoded: Vec<RefCount<RepeatingHitPatterns>> = Vec::new();
+
+    while let Some(repeating) = curr {
+        curr = repeating.get().prev.as_ref().and_then(Weak::upgrade);
+        encoded.push(repeating);
+    }
+
+    encoded.reverse();
+
+    let mut mono_streaks = Vec::new();
+    let mut alternating_mono_patterns = Vec::new();
+    let mut repeating_hit_patterns = Vec::new();
+
+    for repeating in encoded {
+        let repeating = repeating.get();
+        let alternating_start = alternating_mono_patterns.len();
+
+        for alternating in repeating.alternating_mono_patterns.iter() {
+            let mono_start = mono_streaks.len();
+
+            for mono in alternating.get().mono_streaks.iter() {
+                let mono = mono.get();
+
+                // Drumrolls and swells may be part of a streak too
+                let mut notes = mono
+                    .hit_objects
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    .map(|h| {
+                        let h = h.get();
+
+                        (h.idx, h.base_hit_type)
+                    })
+                    .filter(|&(idx, hit_type)| idx < n_diff_objects && hit_type.is_hit());
+
+                let Some((first, hit_type)) = notes.next() else {
+                    continue;
+                };
+
+                let color = if hit_type == HitType::Center {
+                    TaikoColor::Center
+                } else {
+                    TaikoColor::Rim
+                };
+
+                let (last, n_notes) = notes.fold((first, 1), |(_, n), (idx, _)| (idx, n + 1));
+
+                mono_streaks.push(TaikoMonoStreak {
+                    range: range(first, last),
+                    color,
+                    n_notes,
+                });
+            }
+
+            if mono_streaks.len() > mono_start {
+                let first = mono_streaks[mono_start].range;
+                let last = mono_streaks[mono_streaks.len() - 1].range;
+
+                alternating_mono_patterns.push(TaikoAlternatingMonoPattern {
+                    range: first.to(last),
+                    mono_streaks: mono_start..mono_streaks.len(),
+                });
+            }
+        }
+
+        if alternating_mono_patterns.len() > alternating_start {
+            let first = alternating_mono_patterns[alternating_start].range;
+            let last = alternating_mono_patterns[alternating_mono_patterns.len() - 1].range;
+
+            repeating_hit_patterns.push(TaikoRepeatingHitPattern {
+                range: first.to(last),
+                alternating_mono_patterns: alternating_start..alternating_mono_patterns.len(),
+                repetition_interval: (repeating.repetition_interval <= MAX_REPETITION_INTERVAL)
+                    .then_some(repeating.repetition_interval),
+            });
+        }
+    }
+
+    (
+        mono_streaks,
+        alternating_mono_patterns,
+        repeating_hit_patterns,
+    )
+}
+
+fn rhythm_segments(
+    diff_objects: &TaikoDifficultyObjects,
+    n_diff_objects: usize,
+    range: &impl Fn(usize, usize) -> TaikoObjectRange,
+) -> Vec<TaikoRhythmSegment> {
+    let mut rhythm_segments = Vec::new();
+    let mut segment_start = None;
+
+    for (i, h) in diff_objects.iter().take(n_diff_objects).enumerate() {
+        let rhythm = h.get().rhythm;
+
+        match segment_start {
+            None => segment_start = Some((i, rhythm)),
+            // A rhythm with id 0 keeps the previous time between objects
+            Some(_) if rhythm.id == 0 => {}
+            Some((start, start_rhythm)) => {
+                rhythm_segments.push(TaikoRhythmSegment {
+                    range: range(start, i - 1),
+                    ratio: start_rhythm.ratio,
+                    difficulty: start_rhythm.difficulty,
+                });
+
+                segment_start = Some((i, rhythm));
+            }
+        }
+    }
+
+    if let Some((start, rhythm)) = segment_start {
+        rhythm_segments.push(TaikoRhythmSegment {
+            range: range(start, n_diff_objects - 1),
+            ratio: rhythm.ratio,
+            difficulty: rhythm.difficulty,
+        });
+    }
+
+    rhythm_segments
+}
+
+fn stamina_segments(
+    diff_objects: &TaikoDifficultyObjects,
+    n_diff_objects: usize,
+    range: &impl Fn(usize, usize) -> TaikoObjectRange,
+) -> Vec<TaikoStaminaSegment> {
+    let mut stamina = Stamina::new(false);
+    stamina.enable_object_strains();
+
+    {
+        let mut stamina = Skill::new(&mut stamina, diff_objects);
+
+        for h in diff_objects.iter().take(n_diff_objects) {
+            stamina.process(&h.get());
+        }
+    }
+
+    let stamina_strains = stamina.into_object_strains();
+    let max_stamina = stamina_strains.iter().copied().fold(0.0, f64::max);
+    let threshold = max_stamina * STAMINA_HEAVY_RATIO;
+
+    runs(&stamina_strains, |&strain| {
+        strain > 0.0 && strain >= threshold
+    })
+    .into_iter()
+    .filter(|run| run.end() - run.start() + 1 >= STAMINA_HEAVY_MIN_OBJECTS)
+    .map(|run| TaikoStaminaSegment {
+        range: range(*run.start(), *run.end()),
+        peak: stamina_strains[run].iter().copied().fold(0.0, f64::max),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodings_are_consistent() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let patterns = TaikoPatterns::calculate(&Difficulty::new(), &map).unwrap();
+
+        assert!(!patterns.mono_streaks.is_empty());
+        assert!(!patterns.stamina_segments.is_empty());
+
+        let n_notes: usize = patterns.mono_streaks.iter().map(|mono| mono.n_notes).sum();
+        let n_circles = map
+            .hit_objects
+            .iter()
+            .skip(2)
+            .filter(|h| h.is_circle())
+            .count();
+        assert_eq!(n_notes, n_circles);
+
+        for streaks in patterns.mono_streaks.windows(2) {
+            assert!(streaks[0].range.last_object < streaks[1].range.first_object);
+        }
+
+        let covered: usize = patterns
+            .alternating_mono_patterns
+            .iter()
+            .map(|pattern| pattern.mono_streaks.len())
+            .sum();
+        assert_eq!(covered, patterns.mono_streaks.len());
+
+        let covered: usize = patterns
+            .repeating_hit_patterns
+            .iter()
+            .map(|pattern| pattern.alternating_mono_patterns.len())
+            .sum();
+        assert_eq!(covered, patterns.alternating_mono_patterns.len());
+
+        let first = patterns.rhythm_segments.first().unwrap();
+        let last = patterns.rhythm_segments.last().unwrap();
+        assert_eq!(first.range.first_object, 2);
+        assert_eq!(last.range.last_object, map.hit_objects.len() - 1);
+    }
+
+    #[test]
+    fn respects_passed_objects() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let patterns =
+            TaikoPatterns::calculate(&Difficulty::new().passed_objects(100), &map).unwrap();
+
+        let last_object = patterns.rhythm_segments.last().unwrap().range.last_object;
+
+        assert!(patterns
+            .mono_streaks
+            .iter()
+            .all(|mono| mono.range.last_object <= last_object));
+    }
+}
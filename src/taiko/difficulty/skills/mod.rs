@@ -21,4 +21,12 @@ impl TaikoSkills {
             single_color_stamina: Stamina::new(true),
         }
     }
+
+    /// Store the strain value of each object for all skills.
+    pub fn enable_object_strains(&mut self) {
+        self.rhythm.enable_object_strains();
+        self.color.enable_object_strains();
+        self.stamina.enable_object_strains();
+        self.single_color_stamina.enable_object_strains();
+    }
 }
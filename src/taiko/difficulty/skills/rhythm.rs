@@ -146,6 +146,14 @@ impl Rhythm {
         self.inner.get_curr_strain_peaks()
     }
 
+    pub fn enable_object_strains(&mut self) {
+        self.inner.enable_object_strains();
+    }
+
+    pub fn into_object_strains(self) -> Vec<f64> {
+        self.inner.into_object_strains()
+    }
+
     pub fn as_difficulty_value(&self) -> f64 {
         self.inner
             .clone()
@@ -196,6 +204,8 @@ impl Skill<'_, Rhythm> {
         }
 
         let strain_value_at = self.inner.strain_value_at(curr);
+
+        self.inner.inner.save_object_strain(strain_value_at);
         *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
     }
 }
@@ -10,15 +10,29 @@ use crate::{
 
 pub use self::{
     attributes::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
-    difficulty::gradual::TaikoGradualDifficulty,
-    performance::{gradual::TaikoGradualPerformance, TaikoPerformance},
+    difficulty::{
+        gradual::TaikoGradualDifficulty,
+        pattern::{
+            TaikoAlternatingMonoPattern, TaikoColor, TaikoMonoStreak, TaikoObjectRange,
+            TaikoPatterns, TaikoRepeatingHitPattern, TaikoRhythmSegment, TaikoStaminaSegment,
+        },
+    },
+    note::{TaikoNote, TaikoNoteKind, TaikoNotes},
+    performance::{
+        gradual::TaikoGradualPerformance,
+        judgement::{TaikoJudgement, TaikoJudgementError, TaikoJudgementPerformance},
+        TaikoPerformance,
+    },
     score_state::TaikoScoreState,
-    strains::TaikoStrains,
+    strains::{TaikoHardestSections, TaikoObjectStrain, TaikoStrains},
 };
 
+pub(crate) use self::convert::statistics;
+
 mod attributes;
 mod convert;
 mod difficulty;
+mod note;
 mod object;
 mod performance;
 mod score_state;
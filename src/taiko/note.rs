@@ -0,0 +1,182 @@
+use rosu_map::section::{general::GameMode, hit_objects::hit_samples::HitSoundType};
+
+use crate::{
+    model::{
+        hit_object::{HitObjectKind, HoldNote, Spinner},
+        mode::ConvertError,
+    },
+    Beatmap, Difficulty,
+};
+
+use super::{convert::drum_roll_duration, difficulty::start_times, TaikoColor};
+
+/// The kind of a [`TaikoNote`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TaikoNoteKind {
+    /// A note that is hit once, i.e. a don or kat.
+    Hit(TaikoColor),
+    /// A drum roll.
+    Drumroll,
+    /// A swell, also known as spinner.
+    Swell,
+}
+
+/// A hit object of an osu!taiko map as it is played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoNote {
+    /// The kind of note.
+    pub kind: TaikoNoteKind,
+    /// Whether the note is big, i.e. a big don, big kat, or big drum roll.
+    pub big: bool,
+    /// Start time of the note in map time in ms.
+    pub start_time: f64,
+    /// End time of the note in map time in ms.
+    ///
+    /// Only differs from the start time for drum rolls and swells.
+    pub end_time: f64,
+}
+
+/// The notes of an osu!taiko map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoNotes {
+    /// All notes, sorted by their start time.
+    pub notes: Vec<TaikoNote>,
+    /// Whether the map was converted from osu!standard.
+    pub is_convert: bool,
+}
+
+impl TaikoNotes {
+    /// Convert the hit objects of a map into osu!taiko notes.
+    ///
+    /// osu!standard maps are converted. [`Difficulty::passed_objects`] and
+    /// [`Difficulty::passed_time`] are respected, in which case notes after
+    /// the last passed don or kat are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, Difficulty};
+    /// use akatsuki_pp::taiko::{TaikoColor, TaikoNoteKind, TaikoNotes};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    /// let converted = TaikoNotes::calculate(&Difficulty::new(), &map).unwrap();
+    /// assert!(converted.is_convert);
+    ///
+    /// let n_kats = converted
+    ///     .notes
+    ///     .iter()
+    ///     .filter(|note| note.kind == TaikoNoteKind::Hit(TaikoColor::Rim))
+    ///     .count();
+    ///
+    /// println!("{n_kats} kats in the converted map");
+    /// ```
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
+        let difficulty = &*difficulty.resolve_passed_time(|| start_times(&map));
+        let take = difficulty.get_passed_objects();
+
+        let mut notes = Vec::with_capacity(map.hit_objects.len());
+        let mut n_hits = 0;
+
+        for (h, sound) in map.hit_objects.iter().zip(map.hit_sounds.iter()) {
+            if n_hits == take {
+                break;
+            }
+
+            let big = sound.has_flag(HitSoundType::FINISH);
+
+            let (kind, duration) = match h.kind {
+                HitObjectKind::Circle => {
+                    n_hits += 1;
+
+                    let color = if sound.has_flag(HitSoundType::CLAP | HitSoundType::WHISTLE) {
+                        TaikoColor::Rim
+                    } else {
+                        TaikoColor::Center
+                    };
+
+                    (TaikoNoteKind::Hit(color), 0.0)
+                }
+                HitObjectKind::Slider(ref slider) => (
+                    TaikoNoteKind::Drumroll,
+                    drum_roll_duration(&map, h.start_time, slider),
+                ),
+                HitObjectKind::Spinner(Spinner { duration })
+                | HitObjectKind::Hold(HoldNote { duration }) => (TaikoNoteKind::Swell, duration),
+            };
+
+            notes.push(TaikoNote {
+                kind,
+                big: big && kind != TaikoNoteKind::Swell,
+                start_time: h.start_time,
+                end_time: h.start_time + duration,
+            });
+        }
+
+        Ok(Self {
+            notes,
+            is_convert: map.is_convert,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::taiko::Taiko;
+
+    use super::*;
+
+    #[test]
+    fn notes_match_attributes() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let converted = TaikoNotes::calculate(&Difficulty::new(), &map).unwrap();
+        let notes = &converted.notes;
+
+        let attrs = Difficulty::new().calculate_for_mode::<Taiko>(&map).unwrap();
+
+        assert!(converted.is_convert);
+
+        let n_hits = notes
+            .iter()
+            .filter(|note| matches!(note.kind, TaikoNoteKind::Hit(_)))
+            .count();
+
+        assert_eq!(n_hits as u32, attrs.max_combo);
+        assert!(notes.iter().any(|note| note.kind == TaikoNoteKind::Swell));
+        assert!(notes.iter().all(|note| note.end_time >= note.start_time));
+        assert!(notes
+            .windows(2)
+            .all(|pair| pair[0].start_time <= pair[1].start_time));
+
+        let partial = TaikoNotes::calculate(&Difficulty::new().passed_objects(100), &map)
+            .unwrap()
+            .notes;
+
+        assert_eq!(partial[..], notes[..partial.len()]);
+        assert!(matches!(
+            partial.last().unwrap().kind,
+            TaikoNoteKind::Hit(_)
+        ));
+    }
+
+    #[test]
+    fn native_drum_rolls() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let converted = TaikoNotes::calculate(&Difficulty::new(), &map).unwrap();
+        let notes = &converted.notes;
+
+        assert!(!converted.is_convert);
+        assert_eq!(notes.len(), map.hit_objects.len());
+
+        for (note, h) in notes.iter().zip(map.hit_objects.iter()) {
+            match note.kind {
+                TaikoNoteKind::Hit(_) => assert!(h.is_circle()),
+                TaikoNoteKind::Drumroll => {
+                    assert!(h.is_slider());
+                    assert!(note.end_time > note.start_time);
+                }
+                TaikoNoteKind::Swell => assert!(h.is_spinner()),
+            }
+        }
+    }
+}
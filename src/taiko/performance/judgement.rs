@@ -0,0 +1,183 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::{gradual::TaikoGradualPerformance, TaikoPerformanceAttributes, TaikoScoreState};
+
+/// A single judgement of a circle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TaikoJudgement {
+    /// The circle was hit with a 300.
+    Great,
+    /// The circle was hit with a 100.
+    Ok,
+    /// The circle was missed.
+    Miss,
+}
+
+/// Error type when a [`TaikoJudgement`] cannot be processed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TaikoJudgementError {
+    /// All circles have already been judged
+    NoRemainingObjects,
+}
+
+impl Error for TaikoJudgementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for TaikoJudgementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            TaikoJudgementError::NoRemainingObjects => {
+                f.write_str("All circles have already been judged")
+            }
+        }
+    }
+}
+
+/// Gradually calculate the performance attributes of an osu!taiko map
+/// based on individual judgements.
+///
+/// Unlike [`TaikoGradualPerformance`], this type keeps track of the score
+/// state itself. Only circles are judged, drum rolls and swells don't affect
+/// the score state.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::taiko::{TaikoJudgement, TaikoJudgementPerformance};
+///
+/// let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+///
+/// let difficulty = Difficulty::new().mods(64); // DT
+/// let mut gradual = TaikoJudgementPerformance::new(difficulty, &map).unwrap();
+///
+/// for _ in 0..10 {
+///     let attrs = gradual.judge(TaikoJudgement::Great).unwrap();
+///     println!("PP: {}", attrs.pp);
+/// }
+///
+/// // A miss resets the combo
+/// gradual.judge(TaikoJudgement::Miss).unwrap();
+/// gradual.judge(TaikoJudgement::Ok).unwrap();
+///
+/// assert_eq!(gradual.state().max_combo, 10);
+/// ```
+pub struct TaikoJudgementPerformance {
+    gradual: TaikoGradualPerformance,
+    state: TaikoScoreState,
+    curr_combo: u32,
+}
+
+impl TaikoJudgementPerformance {
+    /// Create a new judgement-based performance calculator for osu!taiko
+    /// maps.
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let gradual = TaikoGradualPerformance::new(difficulty, map)?;
+
+        Ok(Self {
+            gradual,
+            state: TaikoScoreState::new(),
+            curr_combo: 0,
+        })
+    }
+
+    /// Process the judgement of the next circle and calculate the
+    /// performance attributes for the resulting score state.
+    pub fn judge(
+        &mut self,
+        judgement: TaikoJudgement,
+    ) -> Result<TaikoPerformanceAttributes, TaikoJudgementError> {
+        if self.gradual.len() == 0 {
+            return Err(TaikoJudgementError::NoRemainingObjects);
+        }
+
+        match judgement {
+            TaikoJudgement::Great => self.state.n300 += 1,
+            TaikoJudgement::Ok => self.state.n100 += 1,
+            TaikoJudgement::Miss => self.state.misses += 1,
+        }
+
+        if judgement == TaikoJudgement::Miss {
+            self.curr_combo = 0;
+        } else {
+            self.curr_combo += 1;
+            self.state.max_combo = self.state.max_combo.max(self.curr_combo);
+        }
+
+        self.gradual
+            .next(self.state)
+            .ok_or(TaikoJudgementError::NoRemainingObjects)
+    }
+
+    /// The current score state.
+    pub const fn state(&self) -> &TaikoScoreState {
+        &self.state
+    }
+
+    /// Returns the amount of remaining circles.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.gradual.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::taiko::TaikoPerformance;
+
+    use super::*;
+
+    #[test]
+    fn full_combo_matches_ss() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let mut gradual = TaikoJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        let mut last = None;
+
+        while gradual.len() > 0 {
+            last = Some(gradual.judge(TaikoJudgement::Great).unwrap());
+        }
+
+        let expected = TaikoPerformance::from(&map).calculate().unwrap();
+
+        assert_eq!(last.unwrap(), expected);
+        assert_eq!(
+            gradual.judge(TaikoJudgement::Great),
+            Err(TaikoJudgementError::NoRemainingObjects)
+        );
+    }
+
+    #[test]
+    fn miss_resets_combo() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let mut gradual = TaikoJudgementPerformance::new(Difficulty::new(), &map).unwrap();
+
+        for _ in 0..5 {
+            gradual.judge(TaikoJudgement::Great).unwrap();
+        }
+
+        gradual.judge(TaikoJudgement::Miss).unwrap();
+
+        for _ in 0..3 {
+            gradual.judge(TaikoJudgement::Ok).unwrap();
+        }
+
+        let state = gradual.state();
+
+        assert_eq!(state.max_combo, 5);
+        assert_eq!(state.n300, 5);
+        assert_eq!(state.n100, 3);
+        assert_eq!(state.misses, 1);
+        assert_eq!(gradual.curr_combo, 3);
+    }
+}
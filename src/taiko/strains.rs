@@ -1,6 +1,11 @@
 use rosu_map::section::general::GameMode;
 
-use crate::{model::mode::ConvertError, taiko::difficulty::DifficultyValues, Beatmap, Difficulty};
+use crate::{
+    any::{sections, HardSection, StrainTimeline},
+    model::mode::ConvertError,
+    taiko::difficulty::DifficultyValues,
+    Beatmap, Difficulty,
+};
 
 /// The result of calculating the strains on a osu!taiko map.
 ///
@@ -18,6 +23,21 @@ pub struct TaikoStrains {
 impl TaikoStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 400.0;
+
+    /// The timing of the strain sections of the given map.
+    pub fn timeline(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+    ) -> Result<StrainTimeline, ConvertError> {
+        let map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
+
+        // The first two hit objects have no difficulty object
+        Ok(StrainTimeline::new(
+            map.hit_objects.get(2).map(|h| h.start_time),
+            Self::SECTION_LEN,
+            difficulty.get_clock_rate(),
+        ))
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<TaikoStrains, ConvertError> {
@@ -30,3 +50,134 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<TaikoStrains, C
         stamina: values.skills.stamina.get_curr_strain_peaks().into_vec(),
     })
 }
+
+/// The strain values of a single hit object of an osu!taiko map.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TaikoObjectStrain {
+    /// Index of the hit object in the converted map.
+    pub idx: usize,
+    /// Start time of the hit object in ms, unaffected by the clock rate.
+    pub start_time: f64,
+    /// Strain value of the color skill.
+    pub color: f64,
+    /// Strain value of the rhythm skill.
+    pub rhythm: f64,
+    /// Strain value of the stamina skill.
+    pub stamina: f64,
+    /// Strain value of the stamina skill considering only a single color.
+    pub single_color_stamina: f64,
+}
+
+pub fn object_strains(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<Vec<TaikoObjectStrain>, ConvertError> {
+    let map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
+    let DifficultyValues { skills, .. } =
+        DifficultyValues::calculate_object_strains(difficulty, &map);
+
+    let color = skills.color.into_object_strains();
+    let rhythm = skills.rhythm.into_object_strains();
+    let stamina = skills.stamina.into_object_strains();
+    let single_color_stamina = skills.single_color_stamina.into_object_strains();
+
+    // The first two hit objects have no difficulty object
+    let object_strains = map
+        .hit_objects
+        .iter()
+        .enumerate()
+        .skip(2)
+        .zip(color.into_iter().zip(rhythm))
+        .zip(stamina.into_iter().zip(single_color_stamina))
+        .map(
+            |(((idx, h), (color, rhythm)), (stamina, single_color_stamina))| TaikoObjectStrain {
+                idx,
+                start_time: h.start_time,
+                color,
+                rhythm,
+                stamina,
+                single_color_stamina,
+            },
+        )
+        .collect();
+
+    Ok(object_strains)
+}
+
+/// The hardest sections of a osu!taiko map for each skill, sorted by their
+/// peak strain in descending order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaikoHardestSections {
+    /// Hardest sections of the color skill.
+    pub color: Vec<HardSection>,
+    /// Hardest sections of the rhythm skill.
+    pub rhythm: Vec<HardSection>,
+    /// Hardest sections of the stamina skill.
+    pub stamina: Vec<HardSection>,
+    /// Hardest sections of the stamina skill considering only a single color.
+    pub single_color_stamina: Vec<HardSection>,
+}
+
+pub fn hardest_sections(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    count: usize,
+) -> Result<TaikoHardestSections, ConvertError> {
+    let object_strains = object_strains(difficulty, map)?;
+
+    let timeline = StrainTimeline::new(
+        object_strains.first().map(|s| s.start_time),
+        TaikoStrains::SECTION_LEN,
+        difficulty.get_clock_rate(),
+    );
+
+    Ok(TaikoHardestSections {
+        color: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.color)),
+            count,
+        ),
+        rhythm: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.rhythm)),
+            count,
+        ),
+        stamina: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.stamina)),
+            count,
+        ),
+        single_color_stamina: sections::hardest_sections(
+            &timeline,
+            object_strains
+                .iter()
+                .map(|s| (s.idx, s.start_time, s.single_color_stamina)),
+            count,
+        ),
+    })
+}
+impl TaikoObjectStrain {
+    /// Perform a difficulty calculation and return the strain values of each
+    /// hit object.
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Result<Vec<Self>, ConvertError> {
+        object_strains(difficulty, map)
+    }
+}
+
+impl TaikoHardestSections {
+    /// Perform a difficulty calculation and return the `count` hardest
+    /// sections for each skill.
+    pub fn calculate(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        count: usize,
+    ) -> Result<Self, ConvertError> {
+        hardest_sections(difficulty, map, count)
+    }
+}
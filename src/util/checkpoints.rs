@@ -0,0 +1,55 @@
+/// Snapshots of a gradual calculator's state, sorted by the amount of
+/// processed objects at the time of the snapshot.
+///
+/// The state before processing any object is always kept so that it's
+/// possible to return to the start at any time. Further snapshots are taken
+/// every `interval` objects, if specified.
+pub struct Checkpoints<T> {
+    interval: usize,
+    snapshots: Vec<(usize, T)>,
+}
+
+impl<T> Checkpoints<T> {
+    pub fn new(initial: T) -> Self {
+        Self {
+            interval: 0,
+            snapshots: vec![(0, initial)],
+        }
+    }
+
+    /// Take a snapshot every `interval` objects; `0` disables snapshots.
+    pub const fn set_interval(&mut self, interval: usize) {
+        self.interval = interval;
+    }
+
+    /// Amount of objects that can be processed from `idx` on until a snapshot
+    /// is due.
+    pub const fn steps_until_due(&self, idx: usize) -> usize {
+        if self.interval == 0 {
+            usize::MAX
+        } else {
+            self.interval - idx % self.interval
+        }
+    }
+
+    /// Store a snapshot for `idx` if one is due and not yet stored.
+    // `usize::is_multiple_of` requires Rust 1.87 which would raise the MSRV
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn record(&mut self, idx: usize, snapshot: impl FnOnce() -> T) {
+        if self.interval == 0 || idx % self.interval != 0 {
+            return;
+        }
+
+        if let Err(i) = self.snapshots.binary_search_by_key(&idx, |(idx, _)| *idx) {
+            self.snapshots.insert(i, (idx, snapshot()));
+        }
+    }
+
+    /// The latest snapshot that was taken at or before `idx`.
+    pub fn latest_until(&self, idx: usize) -> (usize, &T) {
+        let i = self.snapshots.partition_point(|(n, _)| *n <= idx);
+        let (idx, snapshot) = &self.snapshots[i.saturating_sub(1)];
+
+        (*idx, snapshot)
+    }
+}
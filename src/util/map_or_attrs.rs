@@ -3,7 +3,13 @@ use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
 };
 
-use crate::{model::mode::IGameMode, Beatmap};
+use crate::{
+    model::{
+        limits::{CalcLimits, LimitError},
+        mode::IGameMode,
+    },
+    Beatmap, Difficulty,
+};
 
 pub enum MapOrAttrs<'map, M: IGameMode> {
     Map(Cow<'map, Beatmap>),
@@ -30,6 +36,20 @@ impl<'map, M: IGameMode> MapOrAttrs<'map, M> {
             MapOrAttrs::Attrs(ref mut attrs) => attrs,
         }
     }
+
+    /// Check the map against the given [`CalcLimits`].
+    ///
+    /// Attributes were already calculated so they always pass.
+    pub fn check_limits(
+        &self,
+        difficulty: &Difficulty,
+        limits: &CalcLimits,
+    ) -> Result<(), LimitError> {
+        match self {
+            MapOrAttrs::Map(map) => limits.check(difficulty, map),
+            MapOrAttrs::Attrs(_) => Ok(()),
+        }
+    }
 }
 
 impl<M> Clone for MapOrAttrs<'_, M>
@@ -1,12 +1,16 @@
+pub mod checkpoints;
 pub mod float_ext;
 pub mod limited_queue;
 pub mod map_or_attrs;
+pub mod md5;
 pub mod random;
 pub mod sort;
 pub mod special_functions;
 pub mod strains_vec;
 pub mod sync;
 
+use std::ops::RangeInclusive;
+
 pub fn get_precision_adjusted_beat_len(slider_velocity_multiplier: f64, beat_len: f64) -> f64 {
     let slider_velocity_as_beat_len = -100.0 / slider_velocity_multiplier;
 
@@ -18,3 +22,38 @@ pub fn get_precision_adjusted_beat_len(slider_velocity_multiplier: f64, beat_len
 
     beat_len * bpm_multiplier
 }
+
+/// Maximal ranges of consecutive items that fulfill the predicate.
+pub fn runs<T>(items: &[T], f: impl Fn(&T) -> bool) -> Vec<RangeInclusive<usize>> {
+    let mut runs = Vec::new();
+    let mut start = None;
+
+    for (i, item) in items.iter().enumerate() {
+        match (f(item), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                runs.push(s..=i - 1);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        runs.push(s..=items.len() - 1);
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_runs() {
+        let items = [1, 1, 0, 1, 0, 0, 1, 1, 1];
+
+        assert_eq!(runs(&items, |&n| n == 1), vec![0..=1, 3..=3, 6..=8]);
+    }
+}
@@ -100,6 +100,8 @@ macro_rules! test_cases {
         n_fruits: $n_fruits:literal,
         n_droplets: $n_droplets:literal,
         n_tiny_droplets: $n_tiny_droplets:literal,
+        n_hyper_dashes: $n_hyper_dashes:literal,
+        n_edge_dashes: $n_edge_dashes:literal,
         is_convert: $is_convert:literal,
     }) => {
         CatchDifficultyAttributes {
@@ -108,6 +110,8 @@ macro_rules! test_cases {
             n_fruits: $n_fruits,
             n_droplets: $n_droplets,
             n_tiny_droplets: $n_tiny_droplets,
+            n_hyper_dashes: $n_hyper_dashes,
+            n_edge_dashes: $n_edge_dashes,
             is_convert: $is_convert,
         }
     };
@@ -458,6 +462,8 @@ fn basic_catch() {
                 n_fruits: 728,
                 n_droplets: 2,
                 n_tiny_droplets: 263,
+                n_hyper_dashes: 67,
+                n_edge_dashes: 0,
                 is_convert: false,
             };
             HR => {
@@ -466,6 +472,8 @@ fn basic_catch() {
                 n_fruits: 728,
                 n_droplets: 2,
                 n_tiny_droplets: 263,
+                n_hyper_dashes: 72,
+                n_edge_dashes: 7,
                 is_convert: false,
             };
             EZ => {
@@ -474,6 +482,8 @@ fn basic_catch() {
                 n_fruits: 728,
                 n_droplets: 2,
                 n_tiny_droplets: 263,
+                n_hyper_dashes: 21,
+                n_edge_dashes: 46,
                 is_convert: false,
             };
             DT => {
@@ -482,6 +492,8 @@ fn basic_catch() {
                 n_fruits: 728,
                 n_droplets: 2,
                 n_tiny_droplets: 263,
+                n_hyper_dashes: 67,
+                n_edge_dashes: 0,
                 is_convert: false,
             };
         }
@@ -498,6 +510,8 @@ fn convert_catch() {
                 n_fruits: 908,
                 n_droplets: 0,
                 n_tiny_droplets: 159,
+                n_hyper_dashes: 31,
+                n_edge_dashes: 23,
                 is_convert: true,
             };
             HR => {
@@ -506,6 +520,8 @@ fn convert_catch() {
                 n_fruits: 908,
                 n_droplets: 0,
                 n_tiny_droplets: 159,
+                n_hyper_dashes: 45,
+                n_edge_dashes: 27,
                 is_convert: true,
             };
             EZ => {
@@ -514,6 +530,8 @@ fn convert_catch() {
                 n_fruits: 908,
                 n_droplets: 0,
                 n_tiny_droplets: 159,
+                n_hyper_dashes: 22,
+                n_edge_dashes: 21,
                 is_convert: true,
             };
             DT => {
@@ -522,6 +540,8 @@ fn convert_catch() {
                 n_fruits: 908,
                 n_droplets: 0,
                 n_tiny_droplets: 159,
+                n_hyper_dashes: 31,
+                n_edge_dashes: 23,
                 is_convert: true,
             };
         }
@@ -625,6 +645,8 @@ impl AssertEq for CatchDifficultyAttributes {
         assert_eq!(self.n_fruits, expected.n_fruits);
         assert_eq!(self.n_droplets, expected.n_droplets);
         assert_eq!(self.n_tiny_droplets, expected.n_tiny_droplets);
+        assert_eq!(self.n_hyper_dashes, expected.n_hyper_dashes);
+        assert_eq!(self.n_edge_dashes, expected.n_edge_dashes);
     }
 }
 